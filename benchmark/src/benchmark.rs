@@ -1,180 +1,346 @@
-use core::ops::Range;
+use benchmark::config::{Alg, Config, Families};
 use criterion::{criterion_group, criterion_main};
-use criterion::{BenchmarkId, Criterion, PlotConfiguration, SamplingMode};
+use criterion::{BenchmarkGroup, BenchmarkId, Criterion, PlotConfiguration, SamplingMode};
+use heap::HeapTree;
 use lazy_static::lazy_static;
-use rand::Rng;
-use schrage::{custom_heap_impl::*, std_heap_impl::*, std_vecs_impl::*, task::Task};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use schrage::{
+    batch::solve_batch,
+    carlier::*,
+    custom_heap_impl::*,
+    flowshop::*,
+    heuristics::*,
+    soa_impl::*,
+    std_heap_impl::*,
+    task::{QInvariant, RInvariant, Task},
+};
+use std::collections::BinaryHeap;
+use std::fmt::Display;
 use std::time::Duration;
 
-fn gen_uniform(
-    amount: usize,
-    r_vals: &Range<u32>,
-    p_vals: &Range<u32>,
-    q_vals: &Range<u32>,
-) -> Vec<Task> {
-    let mut rng = rand::thread_rng();
-    let mut tasks = Vec::new();
-    for _ in 0..amount {
-        let r = rng.gen_range(r_vals.to_owned());
-        let p = rng.gen_range(p_vals.to_owned());
-        let q = rng.gen_range(q_vals.to_owned());
-        tasks.push(Task { r, p, q });
-    }
-    tasks
+// processing times like in taillard's instances
+fn gen_flow_shop(jobs: usize, machines: usize) -> FlowShop {
+    let mut rng = StdRng::seed_from_u64(CONFIG.seed + (jobs * machines) as u64);
+    FlowShop::new(
+        (0..jobs)
+            .map(|_| (0..machines).map(|_| rng.gen_range(1..100)).collect())
+            .collect(),
+    )
+    .unwrap()
 }
 
-fn gen_tasks(
-    mutiplier_higher_bound: u32,
-    step_by: usize,
-    bound_r: u32,
-    bound_p: u32,
-    bound_q: u32,
-) -> Vec<Vec<Task>> {
-    (1..=mutiplier_higher_bound)
-        .into_iter()
-        .step_by(step_by)
-        .map(|n| ((10 * n) as usize, (0..bound_r), (0..bound_p), (0..bound_q)))
-        .map(|arg| gen_uniform(arg.0, &arg.1, &arg.2, &arg.3))
-        .collect()
-}
+// many small instances solved one after another and as a batch on the rayon pool
+const BATCH_COUNT: usize = 1000;
+const BATCH_SIZES: [usize; 2] = [20, 100];
 
+// every run benches the same instances so results are comparable across commits
 lazy_static! {
-    #[derive(Debug)]
-    static ref DATA: Vec<Vec<Task>> = gen_tasks(30, 3, 1000, 1000, 1000);
+    static ref CONFIG: Config = Config::load().unwrap_or_else(|e| panic!("{e}"));
+    static ref DATA: Vec<Vec<Task>> =
+        CONFIG.uniform_data(0, &CONFIG.uniform_sizes, (1000, 1000, 1000));
+    static ref DATA_FOR_HEAPS: Vec<Vec<Task>> =
+        CONFIG.uniform_data(1, &CONFIG.heap_sizes, (100, 1000, 1000));
+    static ref LONG_DATA: Vec<Vec<Task>> =
+        CONFIG.uniform_data(2, &[CONFIG.long_size], (10000, 10000, 10000));
+    static ref FAMILIES: Families = CONFIG.families(3);
+    static ref BATCHES: Vec<Vec<Vec<Task>>> = BATCH_SIZES
+        .iter()
+        .map(|&n| CONFIG.uniform_data(4, &[n; BATCH_COUNT], (100, 100, 100)))
+        .collect();
+    static ref FIXED: Vec<(String, Vec<Task>)> =
+        CONFIG.fixed_instances().unwrap_or_else(|e| panic!("{e}"));
 }
 
-fn bench_algs_preemptive(c: &mut Criterion) {
-    let sets_of_tasks: &Vec<Vec<Task>> = &*DATA;
-    let mut group = c.benchmark_group("preemptive algs on random uniform data");
+fn configure<M: criterion::measurement::Measurement>(group: &mut BenchmarkGroup<M>) {
     group
-        .sample_size(30)
         .sampling_mode(SamplingMode::Flat)
         .plot_config(PlotConfiguration::default())
         .warm_up_time(Duration::from_secs(1));
+}
 
-    for tasks in sets_of_tasks {
-        group.bench_with_input(
-            BenchmarkId::new("schrage_preemptive_heaps_std_cmax", &tasks.len()),
-            tasks,
-            |bencher, input| bencher.iter(|| schrage_preemptive_heaps_std_cmax(input.clone())),
-        );
-        group.bench_with_input(
-            BenchmarkId::new("schrage_preemptive_custom_heaps_cmax", &tasks.len()),
-            tasks,
-            |bencher, input| bencher.iter(|| schrage_preemptive_custom_heaps_cmax(input.clone())),
-        );
+// every backend on every instance, the instance is cloned because the algorithms take ownership
+fn bench_backends<M: criterion::measurement::Measurement, P: Display>(
+    group: &mut BenchmarkGroup<M>,
+    backends: &[(&'static str, Alg)],
+    suffix: &str,
+    parameter: P,
+    tasks: &Vec<Task>,
+) {
+    for (name, alg) in backends {
         group.bench_with_input(
-            BenchmarkId::new("schrage_preemptive_vecs_cmax", &tasks.len()),
+            BenchmarkId::new(format!("{name}{suffix}"), &parameter),
             tasks,
-            |bencher, input| bencher.iter(|| schrage_preemptive_vecs_cmax(input.clone())),
+            |bencher, input| bencher.iter(|| alg(input.clone())),
         );
     }
+}
+
+fn bench_algs_preemptive(c: &mut Criterion) {
+    let mut group = c.benchmark_group("preemptive algs on random uniform data");
+    configure(&mut group);
+    group.sample_size(30);
+    for tasks in DATA.iter() {
+        bench_backends(&mut group, &CONFIG.preemptive(), "", tasks.len(), tasks);
+    }
     group.finish();
 }
 
 fn bench_algs(c: &mut Criterion) {
-    let sets_of_tasks: &Vec<Vec<Task>> = &*DATA;
     let mut group = c.benchmark_group("Non preemptive algs on random uniform data");
-    group
-        .sampling_mode(SamplingMode::Flat)
-        .plot_config(PlotConfiguration::default())
-        .warm_up_time(Duration::from_secs(1));
+    configure(&mut group);
+    for tasks in DATA.iter() {
+        bench_backends(&mut group, &CONFIG.non_preemptive(), "", tasks.len(), tasks);
+    }
+    group.finish();
+}
 
-    for tasks in sets_of_tasks {
+// the clone of the instance against borrowing it and reusing the heaps
+fn bench_zero_copy(c: &mut Criterion) {
+    let mut group = c.benchmark_group("clone, slice and scratch entry points");
+    configure(&mut group);
+    let mut std_scratch = SchrageScratch::new();
+    let mut custom_scratch = CustomHeapsScratch::new();
+    for tasks in DATA.iter() {
         group.bench_with_input(
-            BenchmarkId::new("schrage_heaps_std_cmax", &tasks.len()),
+            BenchmarkId::new("schrage_heaps_std_cmax", tasks.len()),
             tasks,
             |bencher, input| bencher.iter(|| schrage_heaps_std_cmax(input.clone())),
         );
         group.bench_with_input(
-            BenchmarkId::new("schrage_custom_heaps_cmax", &tasks.len()),
+            BenchmarkId::new("schrage_heaps_std_slice_cmax", tasks.len()),
             tasks,
-            |bencher, input| bencher.iter(|| schrage_custom_heaps_cmax(input.clone())),
+            |bencher, input| bencher.iter(|| schrage_heaps_std_slice_cmax(input)),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("SchrageScratch::schrage_cmax", tasks.len()),
+            tasks,
+            |bencher, input| bencher.iter(|| std_scratch.schrage_cmax(input)),
         );
         group.bench_with_input(
-            BenchmarkId::new("schrage_vecs_sort_q_cmax", &tasks.len()),
+            BenchmarkId::new("schrage_custom_heaps_cmax", tasks.len()),
             tasks,
-            |bencher, input| bencher.iter(|| schrage_vecs_sort_q_cmax(input.clone())),
+            |bencher, input| bencher.iter(|| schrage_custom_heaps_cmax(input.clone())),
         );
         group.bench_with_input(
-            BenchmarkId::new("schrage_vecs_sort_r_cmax", &tasks.len()),
+            BenchmarkId::new("CustomHeapsScratch::schrage_cmax", tasks.len()),
             tasks,
-            |bencher, input| bencher.iter(|| schrage_vecs_sort_r_cmax(input.clone())),
+            |bencher, input| bencher.iter(|| custom_scratch.schrage_cmax(input)),
         );
     }
     group.finish();
 }
 
-lazy_static! {
-    static ref DATA_FOR_HEAPS: Vec<Vec<Task>> = gen_tasks(10000, 1000, 100, 1000, 1000);
+fn bench_improved_algs(c: &mut Criterion) {
+    let mut group = c.benchmark_group("improved algs on random uniform data");
+    configure(&mut group);
+    group.sample_size(10);
+    let algs: [(&'static str, Alg); 4] = [
+        ("schrage_heaps_std_cmax", schrage_heaps_std_cmax),
+        ("potts_cmax", potts_cmax),
+        ("hall_shmoys_cmax", hall_shmoys_cmax),
+        ("carlier_cmax", carlier_cmax),
+    ];
+    for tasks in DATA.iter() {
+        bench_backends(&mut group, &algs, "", tasks.len(), tasks);
+    }
+    group.finish();
 }
 
-fn bench_on_big_data(c: &mut Criterion) {
-    let mut group = c.benchmark_group("heaps algs on lots of random uniform data");
-    let sets_of_tasks = &*DATA_FOR_HEAPS;
-    group
-        .sampling_mode(SamplingMode::Flat)
-        .plot_config(PlotConfiguration::default())
-        .warm_up_time(Duration::from_secs(1));
+// sequential against rayon, the speedup is the ratio of the times of a pair
+fn bench_parallel(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sequential and parallel solving");
+    configure(&mut group);
+    group.sample_size(10);
+    let algs: [(&'static str, Alg); 3] = [
+        ("schrage_heaps_std_cmax", schrage_heaps_std_cmax),
+        ("potts_cmax", potts_cmax),
+        ("carlier_cmax", carlier_cmax),
+    ];
+    for instances in BATCHES.iter() {
+        let parameter = format!("{}x{}", instances.len(), instances[0].len());
+        for (name, alg) in algs {
+            group.bench_with_input(
+                BenchmarkId::new(format!("{name} sequential batch"), &parameter),
+                instances,
+                |bencher, input| {
+                    bencher.iter(|| {
+                        input
+                            .iter()
+                            .map(|tasks| alg(tasks.clone()))
+                            .collect::<Vec<_>>()
+                    })
+                },
+            );
+            group.bench_with_input(
+                BenchmarkId::new(format!("{name} solve_batch"), &parameter),
+                instances,
+                |bencher, input| bencher.iter(|| solve_batch(input.iter().cloned(), alg)),
+            );
+        }
+    }
+    for tasks in DATA.iter() {
+        let algs: [(&'static str, Alg); 2] = [
+            ("carlier_cmax", carlier_cmax),
+            ("carlier_parallel_cmax", carlier_parallel_cmax),
+        ];
+        bench_backends(&mut group, &algs, "", tasks.len(), tasks);
+    }
+    group.finish();
+}
 
-    for tasks in sets_of_tasks {
-        group.bench_with_input(
-            BenchmarkId::new("schrage_preemptive_heaps_std_cmax", &tasks.len()),
-            tasks,
-            |bencher, input| bencher.iter(|| schrage_preemptive_heaps_std_cmax(input.clone())),
-        );
-        group.bench_with_input(
-            BenchmarkId::new("schrage_preemptive_custom_heaps_cmax", &tasks.len()),
-            tasks,
-            |bencher, input| bencher.iter(|| schrage_preemptive_custom_heaps_cmax(input.clone())),
-        );
+fn bench_flow_shop(c: &mut Criterion) {
+    let mut group = c.benchmark_group("flow shop heuristics on random uniform data");
+    configure(&mut group);
+    group.sample_size(20);
+
+    for jobs in [20, 50, 100, 200] {
+        let instance = gen_flow_shop(jobs, 2);
         group.bench_with_input(
-            BenchmarkId::new("schrage_heaps_std_cmax", &tasks.len()),
-            tasks,
-            |bencher, input| bencher.iter(|| schrage_heaps_std_cmax(input.clone())),
+            BenchmarkId::new("johnson 2 machines", jobs),
+            &instance,
+            |bencher, input| bencher.iter(|| johnson_cmax(input)),
         );
         group.bench_with_input(
-            BenchmarkId::new("schrage_custom_heaps_cmax", &tasks.len()),
-            tasks,
-            |bencher, input| bencher.iter(|| schrage_custom_heaps_cmax(input.clone())),
+            BenchmarkId::new("neh 2 machines", jobs),
+            &instance,
+            |bencher, input| bencher.iter(|| neh_cmax(input)),
         );
+        for machines in [5, 10, 20] {
+            let instance = gen_flow_shop(jobs, machines);
+            group.bench_with_input(
+                BenchmarkId::new(format!("neh {} machines", machines), jobs),
+                &instance,
+                |bencher, input| bencher.iter(|| neh_cmax(input)),
+            );
+        }
+    }
+    group.finish();
+}
+
+fn bench_on_big_data(c: &mut Criterion) {
+    let mut group = c.benchmark_group("heaps algs on lots of random uniform data");
+    configure(&mut group);
+    for tasks in DATA_FOR_HEAPS.iter() {
+        bench_backends(&mut group, &CONFIG.heap_backends(), "", tasks.len(), tasks);
     }
+    group.finish();
 }
 
+// the layouts are also compared without the clone and the conversion to arrays,
+// tasks moved through the heaps against u32 indices into r, p, q arrays
 fn single_iter_through_long_data(c: &mut Criterion) {
-    let sets_of_tasks: &Vec<Vec<Task>> =
-        &vec![gen_uniform(2000_000, &(0..10000), &(0..10000), &(0..10000))];
-    let mut group = c.benchmark_group("preemptive algs on random uniform data");
-    group
-        .sample_size(10)
-        .sampling_mode(SamplingMode::Flat)
-        .plot_config(PlotConfiguration::default())
-        .warm_up_time(Duration::from_secs(1));
-    for tasks in sets_of_tasks {
-        group.bench_with_input(
-            BenchmarkId::new("schrage_preemptive_heaps_std_cmax", &tasks.len()),
-            tasks,
-            |bencher, input| bencher.iter(|| schrage_preemptive_heaps_std_cmax(input.clone())),
-        );
+    let mut group = c.benchmark_group("heaps algs on long random uniform data");
+    configure(&mut group);
+    group.sample_size(10);
+    for tasks in LONG_DATA.iter() {
+        bench_backends(&mut group, &CONFIG.heap_backends(), "", tasks.len(), tasks);
+
+        let mut scratch = SchrageScratch::with_capacity(tasks.len());
         group.bench_with_input(
-            BenchmarkId::new("schrage_preemptive_custom_heaps_cmax", &tasks.len()),
+            BenchmarkId::new("SchrageScratch::schrage_cmax tasks", tasks.len()),
             tasks,
-            |bencher, input| bencher.iter(|| schrage_preemptive_custom_heaps_cmax(input.clone())),
+            |bencher, input| bencher.iter(|| scratch.schrage_cmax(input)),
         );
+        let arrays = TaskArrays::from(tasks.as_slice());
+        let mut scratch = SoaScratch::new();
         group.bench_with_input(
-            BenchmarkId::new("schrage_heaps_std_cmax", &tasks.len()),
-            tasks,
-            |bencher, input| bencher.iter(|| schrage_heaps_std_cmax(input.clone())),
-        );
-        group.bench_with_input(
-            BenchmarkId::new("schrage_custom_heaps_cmax", &tasks.len()),
-            tasks,
-            |bencher, input| bencher.iter(|| schrage_custom_heaps_cmax(input.clone())),
+            BenchmarkId::new("SoaScratch::schrage_cmax arrays", tasks.len()),
+            &arrays,
+            |bencher, input| bencher.iter(|| scratch.schrage_cmax(input)),
         );
     }
+    group.finish();
+}
+
+fn bench_families(c: &mut Criterion) {
+    let mut group = c.benchmark_group("heap algs on structured families");
+    configure(&mut group);
+    group.sample_size(30);
+    for (family, sets_of_tasks) in FAMILIES.iter() {
+        for tasks in sets_of_tasks {
+            let suffix = format!(" {family}");
+            bench_backends(
+                &mut group,
+                &CONFIG.heap_backends(),
+                &suffix,
+                tasks.len(),
+                tasks,
+            );
+        }
+    }
+    group.finish();
+}
+
+// the heaps alone, every task is pushed by r and popped, then pushed by q and popped
+fn bench_heaps(c: &mut Criterion) {
+    let mut group = c.benchmark_group("heaps on structured families");
+    configure(&mut group);
+    group.sample_size(30);
+
+    for (family, sets_of_tasks) in FAMILIES.iter() {
+        for tasks in sets_of_tasks {
+            group.bench_with_input(
+                BenchmarkId::new(format!("HeapTree {family}"), tasks.len()),
+                tasks,
+                |bencher, input| {
+                    bencher.iter(|| {
+                        let mut by_r: HeapTree<RInvariant> =
+                            input.iter().map(|t| t.into()).collect();
+                        let mut by_q: HeapTree<QInvariant> = HeapTree::new();
+                        while let Some(task) = by_r.pop() {
+                            by_q.push(task.0.into());
+                        }
+                        while by_q.pop().is_some() {}
+                    })
+                },
+            );
+            group.bench_with_input(
+                BenchmarkId::new(format!("BinaryHeap {family}"), tasks.len()),
+                tasks,
+                |bencher, input| {
+                    bencher.iter(|| {
+                        let mut by_r: BinaryHeap<RInvariant> =
+                            input.iter().map(|t| t.into()).collect();
+                        let mut by_q: BinaryHeap<QInvariant> = BinaryHeap::new();
+                        while let Some(task) = by_r.pop() {
+                            by_q.push(task.0.into());
+                        }
+                        while by_q.pop().is_some() {}
+                    })
+                },
+            );
+        }
+    }
+    group.finish();
+}
+
+fn bench_fixed_instances(c: &mut Criterion) {
+    if FIXED.is_empty() {
+        return;
+    }
+    let mut group = c.benchmark_group("heap algs on fixed instances");
+    configure(&mut group);
+    for (name, tasks) in FIXED.iter() {
+        bench_backends(&mut group, &CONFIG.heap_backends(), "", name, tasks);
+    }
+    group.finish();
 }
 
-criterion_group!(bench, bench_algs, bench_algs_preemptive, bench_on_big_data);
-criterion_group!(bench_data_heaps, bench_on_big_data);
-criterion_group!(bench_single_alot, single_iter_through_long_data);
+// every group is registered, pick groups with a criterion filter
+// e.g. `cargo bench -- "structured families"`
+criterion_group!(
+    bench,
+    bench_algs,
+    bench_algs_preemptive,
+    bench_zero_copy,
+    bench_improved_algs,
+    bench_parallel,
+    bench_flow_shop,
+    bench_on_big_data,
+    single_iter_through_long_data,
+    bench_families,
+    bench_heaps,
+    bench_fixed_instances
+);
 criterion_main!(bench);
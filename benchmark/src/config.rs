@@ -0,0 +1,325 @@
+use schrage::{
+    custom_heap_impl::*,
+    generator::Generator,
+    io::{read_csv, read_schr_data},
+    local_search::LocalSearchConfig,
+    soa_impl::*,
+    std_heap_impl::*,
+    std_vecs_impl::*,
+    task::Task,
+};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+pub type Alg = fn(Vec<Task>) -> u32;
+
+// (family, instances of every family size)
+pub type Families = Vec<(&'static str, Vec<Vec<Task>>)>;
+
+pub const NON_PREEMPTIVE: [(&str, Alg); 5] = [
+    ("schrage_heaps_std_cmax", schrage_heaps_std_cmax),
+    ("schrage_custom_heaps_cmax", schrage_custom_heaps_cmax),
+    ("schrage_soa_heaps_cmax", schrage_soa_heaps_cmax),
+    ("schrage_vecs_sort_q_cmax", schrage_vecs_sort_q_cmax),
+    ("schrage_vecs_sort_r_cmax", schrage_vecs_sort_r_cmax),
+];
+
+pub const PREEMPTIVE: [(&str, Alg); 3] = [
+    (
+        "schrage_preemptive_heaps_std_cmax",
+        schrage_preemptive_heaps_std_cmax,
+    ),
+    (
+        "schrage_preemptive_custom_heaps_cmax",
+        schrage_preemptive_custom_heaps_cmax,
+    ),
+    ("schrage_preemptive_vecs_cmax", schrage_preemptive_vecs_cmax),
+];
+
+const ENV_PREFIX: &str = "SCHRAGE_BENCH_";
+
+// what is benched and on which instances, read from the file in
+// SCHRAGE_BENCH_CONFIG and then from SCHRAGE_BENCH_<KEY> variables,
+// every key is also a line `key = value` of the file
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    pub seed: u64,
+    // sizes of the uniform instances every backend is run on
+    pub uniform_sizes: Vec<usize>,
+    // sizes of the uniform instances for the heap backends
+    pub heap_sizes: Vec<usize>,
+    // the single long instance for the heap backends
+    pub long_size: usize,
+    pub family_sizes: Vec<usize>,
+    // names of the backends, all of them when none
+    pub backends: Option<Vec<String>>,
+    // schr.data or r,p,q csv file
+    pub instances: Option<PathBuf>,
+    // instances of every family and size in the quality experiments
+    pub quality_runs: usize,
+    // budget of the local search on every quality instance
+    pub local_search_iterations: usize,
+    pub local_search_ms: u64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            seed: 2023,
+            uniform_sizes: (1..=30).step_by(3).map(|n| 10 * n).collect(),
+            heap_sizes: (1..=10000).step_by(1000).map(|n| 10 * n).collect(),
+            long_size: 2_000_000,
+            family_sizes: vec![100, 1000, 10000],
+            backends: None,
+            instances: None,
+            quality_runs: 10,
+            local_search_iterations: 1000,
+            local_search_ms: 1000,
+        }
+    }
+}
+
+fn parse_list<T: std::str::FromStr>(key: &str, value: &str) -> Result<Vec<T>, String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(|v| v.parse().map_err(|_| format!("invalid {key} value {v}")))
+        .collect()
+}
+
+fn parse_value<T: std::str::FromStr>(key: &str, value: &str) -> Result<T, String> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid {key} value {value}"))
+}
+
+impl Config {
+    pub fn load() -> Result<Config, String> {
+        let mut config = Config::default();
+        if let Ok(path) = env::var(format!("{ENV_PREFIX}CONFIG")) {
+            let text = fs::read_to_string(&path).map_err(|e| format!("{path}: {e}"))?;
+            config.parse(&text)?;
+        }
+        for (key, value) in env::vars() {
+            if let Some(key) = key.strip_prefix(ENV_PREFIX) {
+                if key != "CONFIG" {
+                    config.set(&key.to_lowercase(), &value)?;
+                }
+            }
+        }
+        Ok(config)
+    }
+
+    // `key = value` lines, empty lines and # comments are skipped
+    pub fn parse(&mut self, text: &str) -> Result<(), String> {
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or(format!("expected key = value, got {line}"))?;
+            self.set(key.trim(), value.trim())?;
+        }
+        Ok(())
+    }
+
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "seed" => self.seed = parse_value(key, value)?,
+            "uniform_sizes" => self.uniform_sizes = parse_list(key, value)?,
+            "heap_sizes" => self.heap_sizes = parse_list(key, value)?,
+            "long_size" => self.long_size = parse_value(key, value)?,
+            "family_sizes" => self.family_sizes = parse_list(key, value)?,
+            "instances" => self.instances = Some(PathBuf::from(value)),
+            "quality_runs" => self.quality_runs = parse_value(key, value)?,
+            "local_search_iterations" => self.local_search_iterations = parse_value(key, value)?,
+            "local_search_ms" => self.local_search_ms = parse_value(key, value)?,
+            "backends" => {
+                let backends: Vec<String> = parse_list(key, value)?;
+                let known: Vec<&str> = NON_PREEMPTIVE
+                    .iter()
+                    .chain(PREEMPTIVE.iter())
+                    .map(|(name, _)| *name)
+                    .collect();
+                if let Some(unknown) = backends.iter().find(|b| !known.contains(&b.as_str())) {
+                    return Err(format!(
+                        "unknown backend {unknown}, expected one of {known:?}"
+                    ));
+                }
+                self.backends = Some(backends);
+            }
+            _ => return Err(format!("unknown key {key}")),
+        }
+        Ok(())
+    }
+
+    fn selected(&self, algs: &[(&'static str, Alg)]) -> Vec<(&'static str, Alg)> {
+        algs.iter()
+            .filter(|(name, _)| {
+                self.backends
+                    .as_ref()
+                    .is_none_or(|backends| backends.iter().any(|b| b == name))
+            })
+            .copied()
+            .collect()
+    }
+
+    pub fn non_preemptive(&self) -> Vec<(&'static str, Alg)> {
+        self.selected(&NON_PREEMPTIVE)
+    }
+
+    pub fn preemptive(&self) -> Vec<(&'static str, Alg)> {
+        self.selected(&PREEMPTIVE)
+    }
+
+    // the selected heap based backends, the vector ones are too slow for big data
+    pub fn heap_backends(&self) -> Vec<(&'static str, Alg)> {
+        let mut algs = self.preemptive();
+        algs.extend(self.non_preemptive());
+        algs.retain(|(name, _)| name.contains("heaps"));
+        algs
+    }
+
+    // uniform instances with r, p and q below the bounds
+    pub fn uniform_data(
+        &self,
+        seed_offset: u64,
+        sizes: &[usize],
+        (bound_r, bound_p, bound_q): (u32, u32, u32),
+    ) -> Vec<Vec<Task>> {
+        let mut generator = Generator::new(self.seed + seed_offset);
+        sizes
+            .iter()
+            .map(|&n| generator.uniform(n, &(0..bound_r), &(0..bound_p), &(0..bound_q)))
+            .collect()
+    }
+
+    // structured families at every family size
+    pub fn families(&self, seed_offset: u64) -> Families {
+        let mut generator = Generator::new(self.seed + seed_offset);
+        let sizes = &self.family_sizes;
+        vec![
+            (
+                "carlier_grabowski",
+                sizes
+                    .iter()
+                    .map(|&n| generator.carlier_grabowski(n, 20))
+                    .collect(),
+            ),
+            (
+                "clustered",
+                sizes
+                    .iter()
+                    .map(|&n| generator.clustered(n, 5, 20))
+                    .collect(),
+            ),
+            (
+                "heavy_tailed",
+                sizes
+                    .iter()
+                    .map(|&n| generator.heavy_tailed(n, 1.5))
+                    .collect(),
+            ),
+            (
+                "hard_for_schrage",
+                sizes
+                    .iter()
+                    .map(|&n| generator.hard_for_schrage(n))
+                    .collect(),
+            ),
+        ]
+    }
+
+    // the default neighbourhoods within the configured budget
+    pub fn local_search(&self) -> LocalSearchConfig {
+        LocalSearchConfig {
+            max_iterations: self.local_search_iterations,
+            time_limit: Some(Duration::from_millis(self.local_search_ms)),
+            ..LocalSearchConfig::default()
+        }
+    }
+
+    pub fn fixed_instances(&self) -> Result<Vec<(String, Vec<Task>)>, String> {
+        let Some(path) = &self.instances else {
+            return Ok(Vec::new());
+        };
+        let instances = if path.extension().is_some_and(|ext| ext == "csv") {
+            read_csv(path).map(|tasks| vec![(path.display().to_string(), tasks)])
+        } else {
+            read_schr_data(path)
+        };
+        instances.map_err(|e| format!("{}: {e}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_sizes() {
+        let config = Config::default();
+        assert_eq!(
+            config.uniform_sizes,
+            vec![10, 40, 70, 100, 130, 160, 190, 220, 250, 280]
+        );
+        assert_eq!(config.heap_sizes.len(), 10);
+        assert_eq!(config.heap_sizes[1], 10010);
+    }
+
+    #[test]
+    fn test_parse() {
+        let mut config = Config::default();
+        config
+            .parse(
+                "# small run\nseed = 7\nfamily_sizes = 10, 20\n\n\
+                 backends = schrage_heaps_std_cmax, schrage_preemptive_vecs_cmax\n",
+            )
+            .unwrap();
+        assert_eq!(config.seed, 7);
+        assert_eq!(config.family_sizes, vec![10, 20]);
+        config
+            .parse("local_search_iterations = 50\nlocal_search_ms = 200\n")
+            .unwrap();
+        let local_search = config.local_search();
+        assert_eq!(local_search.max_iterations, 50);
+        assert_eq!(local_search.time_limit, Some(Duration::from_millis(200)));
+        let names =
+            |algs: Vec<(&'static str, Alg)>| algs.into_iter().map(|a| a.0).collect::<Vec<_>>();
+        assert_eq!(
+            names(config.non_preemptive()),
+            vec!["schrage_heaps_std_cmax"]
+        );
+        assert_eq!(
+            names(config.preemptive()),
+            vec!["schrage_preemptive_vecs_cmax"]
+        );
+        assert_eq!(
+            names(config.heap_backends()),
+            vec!["schrage_heaps_std_cmax"]
+        );
+
+        assert!(config.parse("seed 7").is_err());
+        assert!(config.parse("sizes = 1").is_err());
+        assert!(config.parse("long_size = x").is_err());
+        assert!(config.parse("backends = schrage").is_err());
+    }
+
+    #[test]
+    fn test_seeded_data() {
+        let config = Config::default();
+        let bounds = (1000, 1000, 1000);
+        assert_eq!(
+            config.uniform_data(0, &[50], bounds),
+            config.uniform_data(0, &[50], bounds)
+        );
+        let families = config.families(0);
+        assert_eq!(families.len(), 4);
+        assert!(families.iter().all(|(_, sets)| sets.len() == 3));
+    }
+}
@@ -0,0 +1,91 @@
+use benchmark::config::{Config, Families};
+use schrage::{
+    carlier::carlier_cmax,
+    heuristics::{hall_shmoys_cmax, potts_cmax},
+    local_search::schrage_local_search,
+    std_heap_impl::{schrage_heaps_std_cmax, schrage_preemptive_heaps_std_cmax},
+    task::Task,
+};
+use std::process::ExitCode;
+
+// carlier can need exponentially many branches, it is only run on small instances
+const CARLIER_MAX_TASKS: usize = 100;
+
+type QualityAlg<'a> = dyn Fn(Vec<Task>) -> u32 + 'a;
+
+// relative gap to the preemptive lower bound in percent
+fn gap(cmax: u32, bound: u32) -> f64 {
+    100.0 * (cmax - bound) as f64 / bound.max(1) as f64
+}
+
+// mean and max gap of every algorithm over the instances
+fn report(config: &Config, family: &str, size: &str, instances: &[Vec<Task>]) {
+    // the local search stops within the configured budget
+    let local_search = config.local_search();
+    let local_search_cmax = |tasks: Vec<Task>| schrage_local_search(tasks, &local_search).cmax;
+    let algs: [(&str, &QualityAlg); 5] = [
+        ("schrage", &schrage_heaps_std_cmax),
+        ("potts", &potts_cmax),
+        ("hall_shmoys", &hall_shmoys_cmax),
+        ("local_search", &local_search_cmax),
+        ("carlier", &carlier_cmax),
+    ];
+    let bounds: Vec<u32> = instances
+        .iter()
+        .map(|tasks| schrage_preemptive_heaps_std_cmax(tasks.clone()))
+        .collect();
+    for (name, alg) in algs {
+        if name == "carlier" && instances.iter().any(|t| t.len() > CARLIER_MAX_TASKS) {
+            continue;
+        }
+        let gaps: Vec<f64> = instances
+            .iter()
+            .zip(&bounds)
+            .map(|(tasks, &bound)| gap(alg(tasks.clone()), bound))
+            .collect();
+        let mean = gaps.iter().sum::<f64>() / gaps.len().max(1) as f64;
+        let max = gaps.iter().copied().fold(0.0, f64::max);
+        println!("{family:<18} {size:>8} {name:<14} {mean:>10.3} {max:>10.3}");
+    }
+}
+
+fn main() -> ExitCode {
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    println!(
+        "{:<18} {:>8} {:<14} {:>10} {:>10}",
+        "family", "n", "algorithm", "mean gap %", "max gap %"
+    );
+    // every run draws a fresh set of all families
+    let runs: Vec<Families> = (0..config.quality_runs as u64)
+        .map(|run| config.families(run))
+        .collect();
+    for (family_idx, (family, _)) in runs.first().into_iter().flatten().enumerate() {
+        for (size_idx, size) in config.family_sizes.iter().enumerate() {
+            let instances: Vec<Vec<Task>> = runs
+                .iter()
+                .map(|families| families[family_idx].1[size_idx].clone())
+                .collect();
+            report(&config, family, &size.to_string(), &instances);
+        }
+    }
+
+    match config.fixed_instances() {
+        Ok(fixed) => {
+            for (name, tasks) in fixed {
+                report(&config, "fixed", &name, &[tasks]);
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
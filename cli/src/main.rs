@@ -0,0 +1,261 @@
+use schrage::carlier::carlier;
+use schrage::gantt::{render_ascii, render_svg, schedule_ascii, schedule_svg};
+use schrage::generator::Generator;
+use schrage::heuristics::{hall_shmoys, potts};
+use schrage::io::{read_csv, read_schr_data, to_schr_data};
+use schrage::schedule::Schedule;
+use schrage::std_heap_impl::{schrage_heaps_std, schrage_preemptive_segments};
+use schrage::task::Task;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::process::ExitCode;
+use std::str::FromStr;
+
+const USAGE: &str = "usage:
+  schrage render <schr.data or csv file> [--instance <index or name>]
+                 [--alg <schrage|preemptive|potts|hall-shmoys|carlier>]
+                 [--format <ascii|svg>] [--width <columns>] [--output <file>]
+  schrage generate [--family <uniform|carlier-grabowski|clustered|heavy-tailed|hard>]
+                   [--n <tasks>] [--count <instances>] [--seed <seed>] [--k <carlier k>]
+                   [--clusters <clusters>] [--spread <spread>] [--alpha <pareto shape>]
+                   [--output <file>]";
+
+// positional arguments and --key value pairs
+#[derive(Debug, Default, PartialEq)]
+struct Args {
+    positional: Vec<String>,
+    options: HashMap<String, String>,
+}
+
+impl Args {
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Args, String> {
+        let mut parsed = Args::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.strip_prefix("--") {
+                Some(key) => {
+                    let value = args.next().ok_or(format!("missing value for --{key}"))?;
+                    parsed.options.insert(key.to_owned(), value);
+                }
+                None => parsed.positional.push(arg),
+            }
+        }
+        Ok(parsed)
+    }
+
+    fn get(&self, key: &str) -> Option<&str> {
+        self.options.get(key).map(String::as_str)
+    }
+
+    fn parse_or<T: FromStr>(&self, key: &str, default: T) -> Result<T, String> {
+        match self.get(key) {
+            Some(value) => value
+                .parse()
+                .map_err(|_| format!("invalid --{key} {value}")),
+            None => Ok(default),
+        }
+    }
+}
+
+// csv files hold one instance, schr.data instances are picked by index or name
+fn load_instance(path: &str, instance: Option<&str>) -> Result<(String, Vec<Task>), String> {
+    if path.ends_with(".csv") {
+        let tasks = read_csv(path).map_err(|e| format!("{path}: {e}"))?;
+        return Ok((path.to_owned(), tasks));
+    }
+    let mut instances = read_schr_data(path).map_err(|e| format!("{path}: {e}"))?;
+    let idx = match instance {
+        None => 0,
+        Some(instance) => match instance.parse::<usize>() {
+            Ok(idx) => idx,
+            Err(_) => instances
+                .iter()
+                .position(|(name, _)| name == instance)
+                .ok_or(format!("no instance {instance} in {path}"))?,
+        },
+    };
+    if idx >= instances.len() {
+        return Err(format!("{path} has {} instances", instances.len()));
+    }
+    Ok(instances.swap_remove(idx))
+}
+
+fn render(args: &Args) -> Result<String, String> {
+    let path = args.positional.first().ok_or("missing instance file")?;
+    let (_, tasks) = load_instance(path, args.get("instance"))?;
+    let svg = match args.get("format").unwrap_or("ascii") {
+        "ascii" => false,
+        "svg" => true,
+        format => return Err(format!("unknown format {format}")),
+    };
+    let width = args.parse_or("width", 100)?;
+
+    let schedule: Schedule = match args.get("alg").unwrap_or("schrage") {
+        "schrage" => schrage_heaps_std(tasks).into(),
+        "potts" => potts(tasks).into(),
+        "hall-shmoys" => hall_shmoys(tasks).into(),
+        "carlier" => carlier(tasks).into(),
+        "preemptive" => {
+            let (segments, _) = schrage_preemptive_segments(&tasks);
+            return Ok(match svg {
+                true => render_svg(&tasks, &segments),
+                false => render_ascii(&tasks, &segments, width),
+            });
+        }
+        alg => return Err(format!("unknown algorithm {alg}")),
+    };
+    Ok(match svg {
+        true => schedule_svg(&schedule),
+        false => schedule_ascii(&schedule, width),
+    })
+}
+
+// schr.data instances named data.000, data.001, ...
+fn generate(args: &Args) -> Result<String, String> {
+    let n = args.parse_or("n", 50)?;
+    let count = args.parse_or("count", 1)?;
+    let mut generator = Generator::new(args.parse_or("seed", 0)?);
+    let family = args.get("family").unwrap_or("carlier-grabowski");
+    let k: u32 = args.parse_or("k", 20)?;
+    let clusters = args.parse_or("clusters", 5)?;
+    let spread: u32 = args.parse_or("spread", 20)?;
+    let alpha = args.parse_or("alpha", 1.5)?;
+
+    // the largest time of every family has to fit in u32
+    let horizon = u32::try_from(n).ok().and_then(|n| match family {
+        "carlier-grabowski" => n.checked_mul(k)?.checked_add(1),
+        "clustered" => n.checked_mul(25)?.checked_add(spread),
+        "hard" => n.checked_mul(50)?.checked_add(1),
+        _ => Some(n),
+    });
+    if horizon.is_none() {
+        return Err(format!("times of {family} with --n {n} do not fit in u32"));
+    }
+
+    let mut data = String::new();
+    for idx in 0..count {
+        let tasks = match family {
+            "uniform" => generator.uniform(n, &(0..1000), &(1..1000), &(0..1000)),
+            "carlier-grabowski" => generator.carlier_grabowski(n, k),
+            "clustered" => generator.clustered(n, clusters, spread),
+            "heavy-tailed" => generator.heavy_tailed(n, alpha),
+            "hard" => generator.hard_for_schrage(n),
+            family => return Err(format!("unknown family {family}")),
+        };
+        data.push_str(&to_schr_data(&format!("data.{idx:03}"), &tasks));
+    }
+    Ok(data)
+}
+
+fn output(args: &Args, text: String) -> Result<(), String> {
+    match args.get("output") {
+        Some(path) => fs::write(path, text).map_err(|e| format!("{path}: {e}")),
+        None => {
+            print!("{text}");
+            Ok(())
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let mut args = env::args().skip(1);
+    let command = args.next();
+    let result = Args::parse(args).and_then(|args| match command.as_deref() {
+        Some("render") => render(&args).and_then(|text| output(&args, text)),
+        Some("generate") => generate(&args).and_then(|text| output(&args, text)),
+        _ => Err(USAGE.to_owned()),
+    });
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use schrage::io::parse_schr_data;
+
+    fn args(args: &[&str]) -> Args {
+        Args::parse(args.iter().map(|a| a.to_string())).unwrap()
+    }
+
+    fn schr_data() -> String {
+        format!(
+            "{}/../schrage/test_data/schr.data",
+            env!("CARGO_MANIFEST_DIR")
+        )
+    }
+
+    #[test]
+    fn test_args() {
+        let parsed = args(&["file", "--alg", "carlier", "other"]);
+        assert_eq!(parsed.positional, vec!["file", "other"]);
+        assert_eq!(parsed.get("alg"), Some("carlier"));
+        assert_eq!(parsed.parse_or("width", 10), Ok(10));
+        assert!(args(&["--width", "x"]).parse_or("width", 10).is_err());
+        assert!(Args::parse(vec!["--alg".to_owned()]).is_err());
+    }
+
+    #[test]
+    fn test_render() {
+        let path = schr_data();
+        let chart = render(&args(&[&path, "--instance", "data.000", "--width", "40"])).unwrap();
+        assert!(chart.starts_with("cmax 283\n"));
+        assert_eq!(chart.lines().count(), 1 + 4 + 1);
+
+        let chart = render(&args(&[&path, "--alg", "preemptive", "--format", "svg"])).unwrap();
+        assert!(chart.starts_with("<svg"));
+
+        assert!(render(&args(&[&path, "--instance", "8"])).is_err());
+        assert!(render(&args(&[&path, "--alg", "none"])).is_err());
+    }
+
+    #[test]
+    fn test_generate() {
+        let data = generate(&args(&["--family", "hard", "--n", "7", "--count", "3"])).unwrap();
+        let instances = parse_schr_data(&data).unwrap();
+        assert_eq!(instances.len(), 3);
+        assert_eq!(instances[2].0, "data.002");
+        assert!(instances.iter().all(|(_, tasks)| tasks.len() == 7));
+        assert_eq!(
+            data,
+            generate(&args(&["--family", "hard", "--n", "7", "--count", "3"])).unwrap()
+        );
+        assert!(generate(&args(&["--family", "none"])).is_err());
+
+        let data = generate(&args(&["--family", "clustered", "--n", "0"])).unwrap();
+        assert!(parse_schr_data(&data).unwrap()[0].1.is_empty());
+        let data = generate(&args(&[
+            "--family",
+            "carlier-grabowski",
+            "--n",
+            "5",
+            "--k",
+            "0",
+        ]));
+        assert_eq!(parse_schr_data(&data.unwrap()).unwrap()[0].1.len(), 5);
+
+        // times that do not fit in u32 are rejected before anything is generated
+        for family_args in [
+            &["--family", "clustered", "--spread", "4294967295"][..],
+            &[
+                "--family",
+                "carlier-grabowski",
+                "--n",
+                "5000000",
+                "--k",
+                "1000",
+            ],
+            &["--family", "hard", "--n", "100000000"],
+            &["--family", "uniform", "--n", "4294967296"],
+        ] {
+            assert!(generate(&args(family_args)).is_err());
+        }
+    }
+}
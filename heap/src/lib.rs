@@ -1,8 +1,56 @@
-#[derive(Debug, Default)]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::collections::BinaryHeap;
+use alloc::vec::Vec;
+use core::fmt;
+
+// the heap is full, the element that did not fit is given back
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapacityError<T>(pub T);
+
+impl<T> fmt::Display for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "heap capacity exceeded")
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: fmt::Debug> std::error::Error for CapacityError<T> {}
+
+// max heap storage, lets algorithms run on growable and on fixed capacity heaps
+pub trait Heap<T> {
+    fn push(&mut self, elem: T) -> Result<(), CapacityError<T>>;
+    fn pop(&mut self) -> Option<T>;
+    fn peek(&self) -> Option<&T>;
+    fn is_empty(&self) -> bool;
+    fn clear(&mut self);
+
+    // pushes the elements one by one, growable heaps heapify the whole batch instead
+    fn push_all(&mut self, elems: impl IntoIterator<Item = T>) -> Result<(), CapacityError<T>>
+    where
+        Self: Sized,
+    {
+        for elem in elems {
+            self.push(elem)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
 pub struct HeapTree<T> {
     pub heap: Vec<T>,
 }
 
+// derived default would require T: Default
+impl<T> Default for HeapTree<T> {
+    fn default() -> Self {
+        HeapTree { heap: Vec::new() }
+    }
+}
+
 impl<T: Ord> From<Vec<T>> for HeapTree<T> {
     fn from(vec: Vec<T>) -> HeapTree<T> {
         let mut heap = HeapTree { heap: vec };
@@ -17,11 +65,30 @@ impl<T: Ord> FromIterator<T> for HeapTree<T> {
     }
 }
 
+// the new elements are appended and the whole vec is heapified again
+impl<T: Ord> Extend<T> for HeapTree<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.heap.extend(iter);
+        self.heapify_vec();
+    }
+}
+
 impl<T: Ord> HeapTree<T> {
     pub fn new() -> HeapTree<T> {
         HeapTree { heap: Vec::new() }
     }
 
+    pub fn with_capacity(capacity: usize) -> HeapTree<T> {
+        HeapTree {
+            heap: Vec::with_capacity(capacity),
+        }
+    }
+
+    // keeps the allocated memory
+    pub fn clear(&mut self) {
+        self.heap.clear();
+    }
+
     pub fn push(&mut self, elem: T) {
         self.heap.push(elem);
         self.sift_up(self.heap.len() - 1);
@@ -76,7 +143,7 @@ impl<T: Ord> HeapTree<T> {
     }
 
     pub fn peek(&self) -> Option<&T> {
-        self.heap.get(0)
+        self.heap.first()
     }
 
     // assumeses that self.heap is random distributed vec
@@ -87,6 +154,181 @@ impl<T: Ord> HeapTree<T> {
     }
 }
 
+impl<T: Ord> Heap<T> for HeapTree<T> {
+    fn push(&mut self, elem: T) -> Result<(), CapacityError<T>> {
+        HeapTree::push(self, elem);
+        Ok(())
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        HeapTree::pop(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        HeapTree::peek(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        HeapTree::is_empty(self)
+    }
+
+    fn clear(&mut self) {
+        HeapTree::clear(self)
+    }
+
+    fn push_all(&mut self, elems: impl IntoIterator<Item = T>) -> Result<(), CapacityError<T>> {
+        self.extend(elems);
+        Ok(())
+    }
+}
+
+impl<T: Ord> Heap<T> for BinaryHeap<T> {
+    fn push(&mut self, elem: T) -> Result<(), CapacityError<T>> {
+        BinaryHeap::push(self, elem);
+        Ok(())
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        BinaryHeap::pop(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        BinaryHeap::peek(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        BinaryHeap::is_empty(self)
+    }
+
+    fn clear(&mut self) {
+        BinaryHeap::clear(self)
+    }
+
+    fn push_all(&mut self, elems: impl IntoIterator<Item = T>) -> Result<(), CapacityError<T>> {
+        self.extend(elems);
+        Ok(())
+    }
+}
+
+// HeapTree with at most N elements kept inline, it never allocates
+// the first len slots are always Some so they compare like the elements
+#[derive(Debug)]
+pub struct ArrayHeap<T, const N: usize> {
+    heap: [Option<T>; N],
+    len: usize,
+}
+
+impl<T, const N: usize> Default for ArrayHeap<T, N> {
+    fn default() -> Self {
+        ArrayHeap {
+            heap: core::array::from_fn(|_| None),
+            len: 0,
+        }
+    }
+}
+
+impl<T: Ord, const N: usize> ArrayHeap<T, N> {
+    pub fn new() -> ArrayHeap<T, N> {
+        ArrayHeap::default()
+    }
+
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        for slot in &mut self.heap[..self.len] {
+            *slot = None;
+        }
+        self.len = 0;
+    }
+
+    pub fn push(&mut self, elem: T) -> Result<(), CapacityError<T>> {
+        if self.len == N {
+            return Err(CapacityError(elem));
+        }
+        self.heap[self.len] = Some(elem);
+        self.len += 1;
+        self.sift_up(self.len - 1);
+        Ok(())
+    }
+
+    // same moves as HeapTree, the last element takes the place of the top one
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        self.heap.swap(0, self.len);
+        let result = self.heap[self.len].take();
+        self.sift_down(0);
+        result
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.heap.first().and_then(Option::as_ref)
+    }
+
+    fn sift_up(&mut self, starting_node: usize) {
+        let mut current = starting_node;
+        let mut parent = (current.saturating_sub(1)) / 2;
+        while current > 0 && self.heap[current] > self.heap[parent] {
+            self.heap.swap(current, parent);
+            current = parent;
+            parent = (current.saturating_sub(1)) / 2;
+        }
+    }
+
+    fn sift_down(&mut self, mut index: usize) {
+        loop {
+            let left = 2 * index + 1;
+            let right = 2 * index + 2;
+            let mut largest = index;
+            if left < self.len && self.heap[left] > self.heap[largest] {
+                largest = left;
+            }
+            if right < self.len && self.heap[right] > self.heap[largest] {
+                largest = right;
+            }
+            if largest == index {
+                break;
+            }
+            self.heap.swap(index, largest);
+            index = largest;
+        }
+    }
+}
+
+impl<T: Ord, const N: usize> Heap<T> for ArrayHeap<T, N> {
+    fn push(&mut self, elem: T) -> Result<(), CapacityError<T>> {
+        ArrayHeap::push(self, elem)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        ArrayHeap::pop(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        ArrayHeap::peek(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        ArrayHeap::is_empty(self)
+    }
+
+    fn clear(&mut self) {
+        ArrayHeap::clear(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -99,6 +341,18 @@ mod tests {
         assert_eq!(heap.heap, vec![9, 8, 7, 4, 5, 6, 3, 2, 1]);
     }
 
+    #[test]
+    fn test_extend_and_clear() {
+        let mut heap = HeapTree::with_capacity(8);
+        heap.push(5);
+        heap.extend(vec![1, 9, 3]);
+        assert_eq!(heap.pop(), Some(9));
+        assert_eq!(heap.pop(), Some(5));
+        heap.clear();
+        assert!(heap.is_empty());
+        assert!(heap.heap.capacity() >= 8);
+    }
+
     #[test]
     fn test_push() {
         let mut heap = HeapTree::new();
@@ -126,6 +380,66 @@ mod tests {
         assert_eq!(heap.pop(), None);
     }
 
+    #[test]
+    fn test_array_heap() {
+        let mut heap: ArrayHeap<u32, 4> = ArrayHeap::new();
+        assert_eq!(heap.peek(), None);
+        for elem in [3, 1, 4, 1] {
+            heap.push(elem).unwrap();
+        }
+        assert_eq!(heap.push(5), Err(CapacityError(5)));
+        assert_eq!(heap.len(), heap.capacity());
+        assert_eq!(heap.peek(), Some(&4));
+        assert_eq!(heap.pop(), Some(4));
+        heap.push(9).unwrap();
+        let popped: Vec<u32> = core::iter::from_fn(|| heap.pop()).collect();
+        assert_eq!(popped, vec![9, 3, 1, 1]);
+        assert_eq!(heap.pop(), None);
+
+        heap.push(2).unwrap();
+        heap.clear();
+        assert!(heap.is_empty());
+        assert_eq!(ArrayHeap::<u32, 0>::new().push(1), Err(CapacityError(1)));
+    }
+
+    // both heaps move the elements the same way, so equal keys come out in the same order
+    #[test]
+    fn test_array_heap_matches_heap_tree() {
+        let mut array: ArrayHeap<u32, 64> = ArrayHeap::new();
+        let mut tree = HeapTree::new();
+        let same = |array: &ArrayHeap<u32, 64>, tree: &HeapTree<u32>| {
+            let elems: Vec<u32> = array.heap[..array.len].iter().flatten().copied().collect();
+            elems == tree.heap
+        };
+        for i in 0..64 {
+            array.push(i * 7 % 5).unwrap();
+            tree.push(i * 7 % 5);
+            assert!(same(&array, &tree));
+            if i % 3 == 0 {
+                assert_eq!(array.pop(), tree.pop());
+                assert!(same(&array, &tree));
+            }
+        }
+        while !tree.is_empty() {
+            assert_eq!(array.pop(), tree.pop());
+            assert!(same(&array, &tree));
+        }
+    }
+
+    #[test]
+    fn test_push_all() {
+        let mut tree = HeapTree::new();
+        Heap::push_all(&mut tree, [1, 2, 3, 4, 5, 6, 7, 8, 9]).unwrap();
+        assert_eq!(
+            tree.heap,
+            HeapTree::from(vec![1, 2, 3, 4, 5, 6, 7, 8, 9]).heap
+        );
+
+        let mut array: ArrayHeap<u32, 4> = ArrayHeap::new();
+        assert_eq!(array.push_all([3, 1, 4, 1, 5]), Err(CapacityError(5)));
+        assert_eq!(array.pop(), Some(4));
+    }
+
     #[test]
     fn test_sift_down() {
         let mut heap = HeapTree::from(vec![1, 2, 3, 4, 5, 6, 7, 8, 9]);
@@ -0,0 +1,68 @@
+use crate::task::Task;
+use rayon::prelude::*;
+
+// solves every instance with the backend on the rayon pool,
+// results are in the order of the instances
+// run it inside `ThreadPool::install` to use a pool other than the global one
+pub fn solve_batch<I, F, R>(instances: I, backend: F) -> Vec<R>
+where
+    I: IntoIterator<Item = Vec<Task>>,
+    F: Fn(Vec<Task>) -> R + Sync + Send,
+    R: Send,
+{
+    let instances: Vec<Vec<Task>> = instances.into_iter().collect();
+    instances.into_par_iter().map(backend).collect()
+}
+
+// same as solve_batch for instances that are borrowed, e.g. with the slice entry points
+pub fn solve_batch_slices<'a, I, F, R>(instances: I, backend: F) -> Vec<R>
+where
+    I: IntoIterator<Item = &'a [Task]>,
+    F: Fn(&[Task]) -> R + Sync + Send,
+    R: Send,
+{
+    let instances: Vec<&[Task]> = instances.into_iter().collect();
+    instances.into_par_iter().map(backend).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::Generator;
+    use crate::std_heap_impl::{
+        schrage_heaps_std, schrage_heaps_std_cmax, schrage_heaps_std_slice_cmax,
+    };
+
+    #[test]
+    fn test_solve_batch_keeps_order() {
+        let mut generator = Generator::new(7);
+        let instances: Vec<Vec<Task>> = (0..200)
+            .map(|n| generator.uniform(n % 30, &(0..100), &(1..50), &(0..100)))
+            .collect();
+        let expected: Vec<u32> = instances
+            .iter()
+            .map(|tasks| schrage_heaps_std_cmax(tasks.clone()))
+            .collect();
+
+        assert_eq!(
+            solve_batch(instances.clone(), schrage_heaps_std_cmax),
+            expected
+        );
+        assert_eq!(
+            solve_batch_slices(
+                instances.iter().map(Vec::as_slice),
+                schrage_heaps_std_slice_cmax
+            ),
+            expected
+        );
+        let schedules = solve_batch(instances.clone(), schrage_heaps_std);
+        for (tasks, schedule) in instances.into_iter().zip(schedules) {
+            assert_eq!(schedule, schrage_heaps_std(tasks));
+        }
+    }
+
+    #[test]
+    fn test_solve_batch_empty() {
+        assert!(solve_batch(Vec::new(), schrage_heaps_std_cmax).is_empty());
+    }
+}
@@ -0,0 +1,297 @@
+use crate::task::{QInvariant, RInvariant, Task};
+use alloc::collections::BinaryHeap;
+use alloc::vec::Vec;
+use core::cmp::{max, min};
+
+// intervals [start, end) in which the machine can not work
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Calendar {
+    blocked: Vec<(u32, u32)>,
+}
+
+impl Calendar {
+    // intervals are sorted and overlapping ones are merged
+    pub fn new(mut blocked: Vec<(u32, u32)>) -> Calendar {
+        blocked.retain(|(start, end)| start < end);
+        blocked.sort();
+        let mut merged: Vec<(u32, u32)> = Vec::with_capacity(blocked.len());
+        for (start, end) in blocked {
+            match merged.last_mut() {
+                Some(last) if start <= last.1 => last.1 = max(last.1, end),
+                _ => merged.push((start, end)),
+            }
+        }
+        Calendar { blocked: merged }
+    }
+
+    pub fn blocked(&self) -> &[(u32, u32)] {
+        &self.blocked
+    }
+
+    // windows that end after t
+    fn after(&self, t: u32) -> &[(u32, u32)] {
+        &self.blocked[self.blocked.partition_point(|&(_, end)| end <= t)..]
+    }
+
+    // first time from t on when the machine works
+    pub fn available_from(&self, t: u32) -> u32 {
+        match self.after(t).first() {
+            Some(&(start, end)) if start <= t => end,
+            _ => t,
+        }
+    }
+
+    // earliest start from t on of a task that has to run without interruption,
+    // a task without work needs no machine time and fits anywhere
+    pub fn fit(&self, t: u32, p: u32) -> u32 {
+        if p == 0 {
+            return t;
+        }
+        let mut start = t;
+        for &(window_start, window_end) in self.after(t) {
+            if start + p <= window_start {
+                break;
+            }
+            start = max(start, window_end);
+        }
+        start
+    }
+
+    // time at which p units of work started at t are done when the work is split around windows
+    pub fn finish_split(&self, t: u32, p: u32) -> u32 {
+        if p == 0 {
+            return t;
+        }
+        let mut current = t;
+        let mut remaining = p;
+        for &(window_start, window_end) in self.after(t) {
+            if window_start > current {
+                let run = min(remaining, window_start - current);
+                current += run;
+                remaining -= run;
+                if remaining == 0 {
+                    return current;
+                }
+            }
+            current = max(current, window_end);
+        }
+        current + remaining
+    }
+
+    // units of work that can be done between from and to
+    pub fn work_between(&self, from: u32, to: u32) -> u32 {
+        let blocked: u32 = self
+            .after(from)
+            .iter()
+            .take_while(|&&(start, _)| start < to)
+            .map(|&(start, end)| min(end, to) - max(start, from))
+            .sum();
+        to - from - blocked
+    }
+}
+
+// schrage where a task is only started when it fits before the next blocked window,
+// of the ready tasks that fit the one with the largest q is chosen,
+// when none fits the machine waits until the first one does or the next task
+// is released and chooses again
+pub fn schrage_calendar(tasks: Vec<Task>, calendar: &Calendar) -> (Vec<Task>, u32) {
+    let mut available_tasks: BinaryHeap<QInvariant> = BinaryHeap::new();
+    let mut unavailable_tasks: BinaryHeap<RInvariant> = tasks.iter().map(|t| t.into()).collect();
+    let mut waiting: Vec<Task> = Vec::new();
+    let mut t = 0;
+    let mut cmax = 0;
+    let mut order = Vec::new();
+
+    while !available_tasks.is_empty() || !unavailable_tasks.is_empty() {
+        while !unavailable_tasks.is_empty() && unavailable_tasks.peek().unwrap().0.r <= t {
+            let task = unavailable_tasks.pop().unwrap().0;
+            available_tasks.push(task.into());
+        }
+        if available_tasks.is_empty() {
+            t = unavailable_tasks.peek().unwrap().0.r;
+            continue;
+        }
+
+        // tasks that do not fit are put aside until one that fits is found
+        let mut start = u32::MAX;
+        let mut chosen = None;
+        while let Some(QInvariant(task)) = available_tasks.pop() {
+            let fit = calendar.fit(t, task.p);
+            if fit == t {
+                chosen = Some(task);
+                break;
+            }
+            start = min(start, fit);
+            waiting.push(task);
+        }
+        available_tasks.extend(waiting.drain(..).map(QInvariant::from));
+        let Some(task) = chosen else {
+            // a task released in the meantime may fit before the window
+            t = match unavailable_tasks.peek() {
+                Some(next) => min(start, next.0.r),
+                None => start,
+            };
+            continue;
+        };
+
+        t += task.p;
+        cmax = max(cmax, t + task.q);
+        order.push(task);
+    }
+
+    (order, cmax)
+}
+
+pub fn schrage_calendar_cmax(tasks: Vec<Task>, calendar: &Calendar) -> u32 {
+    schrage_calendar(tasks, calendar).1
+}
+
+// preemptive schrage where work is also split around the blocked windows,
+// the choice is made again at every ready time
+pub fn schrage_preemptive_calendar_cmax(tasks: Vec<Task>, calendar: &Calendar) -> u32 {
+    let mut available_tasks: BinaryHeap<QInvariant> = BinaryHeap::new();
+    let mut unavailable_tasks: BinaryHeap<RInvariant> = tasks.iter().map(|t| t.into()).collect();
+    let mut t = 0;
+    let mut cmax = 0;
+
+    while !available_tasks.is_empty() || !unavailable_tasks.is_empty() {
+        while !unavailable_tasks.is_empty() && unavailable_tasks.peek().unwrap().0.r <= t {
+            let task = unavailable_tasks.pop().unwrap().0;
+            available_tasks.push(task.into());
+        }
+        if available_tasks.is_empty() {
+            t = unavailable_tasks.peek().unwrap().0.r;
+            continue;
+        }
+
+        let mut task = available_tasks.pop().unwrap().0;
+        let finish = calendar.finish_split(t, task.p);
+        if let Some(next) = unavailable_tasks.peek().map(|next| next.0.r) {
+            if next < finish {
+                // work until the next ready time and choose again
+                task.p -= calendar.work_between(t, next);
+                available_tasks.push(task.into());
+                t = next;
+                continue;
+            }
+        }
+
+        t = finish;
+        cmax = max(cmax, t + task.q);
+    }
+
+    cmax
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::std_heap_impl::{schrage_heaps_std, schrage_preemptive_heaps_std_cmax};
+
+    macro_rules! tasks {
+        () => {
+            vec![
+                Task::new(30, 3, 8),
+                Task::new(20, 4, 21),
+                Task::new(10, 5, 7),
+                Task::new(11, 7, 24),
+                Task::new(30, 2, 0),
+                Task::new(13, 6, 26),
+                Task::new(0, 6, 17),
+            ]
+        };
+    }
+
+    #[test]
+    fn test_calendar() {
+        let calendar = Calendar::new(vec![(10, 12), (3, 6), (5, 8), (20, 20)]);
+        assert_eq!(calendar.blocked(), &[(3, 8), (10, 12)]);
+        assert_eq!(calendar.available_from(2), 2);
+        assert_eq!(calendar.available_from(4), 8);
+        assert_eq!(calendar.available_from(12), 12);
+        assert_eq!(calendar.fit(0, 3), 0);
+        assert_eq!(calendar.fit(0, 4), 12);
+        assert_eq!(calendar.fit(8, 2), 8);
+        assert_eq!(calendar.finish_split(0, 5), 10);
+        assert_eq!(calendar.finish_split(4, 5), 15);
+        assert_eq!(calendar.work_between(0, 20), 13);
+        assert_eq!(calendar.work_between(4, 11), 2);
+    }
+
+    #[test]
+    fn test_empty_calendar() {
+        let calendar = Calendar::default();
+        assert_eq!(
+            schrage_calendar(tasks!(), &calendar),
+            schrage_heaps_std(tasks!())
+        );
+        assert_eq!(
+            schrage_preemptive_calendar_cmax(tasks!(), &calendar),
+            schrage_preemptive_heaps_std_cmax(tasks!())
+        );
+    }
+
+    #[test]
+    fn test_single_task() {
+        let calendar = Calendar::new(vec![(3, 6)]);
+        // does not fit before the window, starts at 6
+        assert_eq!(
+            schrage_calendar_cmax(vec![Task::new(0, 5, 0)], &calendar),
+            11
+        );
+        // 0..3 and 6..8
+        assert_eq!(
+            schrage_preemptive_calendar_cmax(vec![Task::new(0, 5, 0)], &calendar),
+            8
+        );
+    }
+
+    #[test]
+    fn test_two_tasks() {
+        let calendar = Calendar::new(vec![(3, 5)]);
+        let tasks = vec![Task::new(0, 4, 10), Task::new(0, 2, 0)];
+        // the first task does not fit before the window but the second one does, 0..2,
+        // then the first task waits for the window to end, 5..9
+        let (order, cmax) = schrage_calendar(tasks.clone(), &calendar);
+        assert_eq!(order, vec![Task::new(0, 2, 0), Task::new(0, 4, 10)]);
+        assert_eq!(cmax, 19);
+        // 0..3 and 5..6, then 6..8
+        assert_eq!(schrage_preemptive_calendar_cmax(tasks, &calendar), 16);
+    }
+
+    #[test]
+    fn test_release_during_window() {
+        let calendar = Calendar::new(vec![(2, 10)]);
+        let tasks = vec![Task::new(0, 5, 1), Task::new(6, 1, 20)];
+        // the window is waited out, by then the task with larger q is ready
+        let (order, cmax) = schrage_calendar(tasks.clone(), &calendar);
+        assert_eq!(order, vec![Task::new(6, 1, 20), Task::new(0, 5, 1)]);
+        assert_eq!(cmax, 31);
+        // 0..2 of the first task, at 10 the second one 10..11, then 11..14
+        assert_eq!(schrage_preemptive_calendar_cmax(tasks, &calendar), 31);
+    }
+
+    #[test]
+    fn test_release_before_window() {
+        let calendar = Calendar::new(vec![(3, 10)]);
+        let tasks = vec![Task::new(0, 5, 0), Task::new(1, 1, 100)];
+        // the first task waits for the window to end, the second one is released
+        // in the meantime and runs 1..2, then the first one 10..15
+        let (order, cmax) = schrage_calendar(tasks, &calendar);
+        assert_eq!(order, vec![Task::new(1, 1, 100), Task::new(0, 5, 0)]);
+        assert_eq!(cmax, 102);
+    }
+
+    #[test]
+    fn test_zero_length_task_in_window() {
+        let calendar = Calendar::new(vec![(3, 6)]);
+        assert_eq!(calendar.fit(4, 0), 4);
+        assert_eq!(calendar.finish_split(4, 0), 4);
+        let tasks = vec![Task::new(0, 3, 0), Task::new(4, 0, 7)];
+        // 0..3, the second task needs no machine time and is done at its release
+        let (order, cmax) = schrage_calendar(tasks.clone(), &calendar);
+        assert_eq!(order, vec![Task::new(0, 3, 0), Task::new(4, 0, 7)]);
+        assert_eq!(cmax, 11);
+        assert_eq!(schrage_preemptive_calendar_cmax(tasks, &calendar), 11);
+    }
+}
@@ -0,0 +1,379 @@
+use crate::schedule::{cmax, critical_path, order_indices};
+use crate::std_heap_impl::SchrageScratch;
+use crate::task::Task;
+use alloc::{vec, vec::Vec};
+use core::cmp::{max, min};
+#[cfg(feature = "parallel")]
+use std::sync::atomic::{AtomicU32, Ordering};
+#[cfg(feature = "parallel")]
+use std::sync::Mutex;
+
+// branch and bound state, tasks are the instance modified along the current branch
+struct Carlier {
+    tasks: Vec<Task>,
+    upper_bound: u32,
+    best: Vec<usize>,
+    // the search stops once a schedule this good is found
+    target: u32,
+    // reused by every node, the order buffer is taken while a node uses it
+    scratch: SchrageScratch,
+    order: Vec<Task>,
+}
+
+impl Carlier {
+    fn new(tasks: Vec<Task>, upper_bound: u32, target: u32) -> Carlier {
+        let n = tasks.len();
+        Carlier {
+            tasks,
+            upper_bound,
+            best: Vec::new(),
+            target,
+            scratch: SchrageScratch::with_capacity(n),
+            order: Vec::with_capacity(n),
+        }
+    }
+
+    fn solve(&mut self) {
+        if self.upper_bound <= self.target {
+            return;
+        }
+        let Some(split) = self.split() else {
+            return;
+        };
+        let c_idx = split.c_idx;
+        let interference = self.tasks[c_idx];
+
+        // interference task after the block
+        self.tasks[c_idx] = split.after_block(interference);
+        self.branch(&split);
+        self.tasks[c_idx] = interference;
+
+        // interference task before the block
+        self.tasks[c_idx] = split.before_block(interference);
+        self.branch(&split);
+        self.tasks[c_idx] = interference;
+
+        for (idx, task) in split.eliminated.into_iter().rev() {
+            self.tasks[idx] = task;
+        }
+    }
+
+    // schrage schedule of the node, None when it is optimal for this branch,
+    // otherwise the block to branch on, eliminations are already applied
+    fn split(&mut self) -> Option<Split> {
+        let mut order = core::mem::take(&mut self.order);
+        let u = self.scratch.schrage_into(&self.tasks, &mut order);
+        let indices = order_indices(&order, &self.tasks);
+        if u < self.upper_bound {
+            self.upper_bound = u;
+            self.best = indices.clone();
+        }
+
+        // without interference task the schrage schedule is optimal for this branch
+        let block = critical_path(&order).and_then(|path| Some((path.c?, path.b)));
+        let Some((c, b)) = block else {
+            self.order = order;
+            return None;
+        };
+
+        let block = &order[c + 1..=b];
+        let r = block.iter().map(|t| t.r).min().unwrap();
+        let q = block.iter().map(|t| t.q).min().unwrap();
+        let p = block.iter().fold(0u32, |p, t| p.saturating_add(t.p));
+        self.order = order;
+
+        let eliminated = self.eliminate(&indices[c + 1..=b], r, p, q);
+        Some(Split {
+            c_idx: indices[c],
+            r,
+            p,
+            q,
+            eliminated,
+        })
+    }
+
+    // tasks too long to be scheduled on one side of the block without reaching
+    // the upper bound are forced to the other side
+    // returns the original values of modified tasks so they can be restored
+    // the sums saturate, a saturated sum is at least the upper bound anyway
+    fn eliminate(&mut self, block: &[usize], r: u32, p: u32, q: u32) -> Vec<(usize, Task)> {
+        let mut in_block = vec![false; self.tasks.len()];
+        for &idx in block {
+            in_block[idx] = true;
+        }
+
+        let rp = r.saturating_add(p);
+        let pq = p.saturating_add(q);
+        let mut eliminated = Vec::new();
+        for (idx, &in_block) in in_block.iter().enumerate() {
+            let task = self.tasks[idx];
+            if in_block || task.p.saturating_add(rp).saturating_add(q) <= self.upper_bound {
+                continue;
+            }
+            if task.r.saturating_add(task.p).saturating_add(pq) >= self.upper_bound {
+                eliminated.push((idx, task));
+                self.tasks[idx].r = max(task.r, rp);
+            } else if rp.saturating_add(task.p).saturating_add(task.q) >= self.upper_bound {
+                eliminated.push((idx, task));
+                self.tasks[idx].q = max(task.q, pq);
+            }
+        }
+        eliminated
+    }
+
+    fn lower_bound(&mut self, split: &Split) -> u32 {
+        max(
+            split.block_bound(self.tasks[split.c_idx]),
+            self.scratch.schrage_preemptive_cmax(&self.tasks),
+        )
+    }
+
+    fn branch(&mut self, split: &Split) {
+        if self.lower_bound(split) < self.upper_bound {
+            self.solve();
+        }
+    }
+}
+
+// block of the critical path after the interference task c_idx,
+// eliminated are the original values of tasks forced to one side of it
+struct Split {
+    c_idx: usize,
+    r: u32,
+    p: u32,
+    q: u32,
+    eliminated: Vec<(usize, Task)>,
+}
+
+impl Split {
+    fn after_block(&self, interference: Task) -> Task {
+        Task {
+            r: max(interference.r, self.r.saturating_add(self.p)),
+            ..interference
+        }
+    }
+
+    fn before_block(&self, interference: Task) -> Task {
+        Task {
+            q: max(interference.q, self.q.saturating_add(self.p)),
+            ..interference
+        }
+    }
+
+    fn block_bound(&self, c: Task) -> u32 {
+        let (r, p, q) = (self.r, self.p, self.q);
+        max(
+            r.saturating_add(p).saturating_add(q),
+            min(r, c.r)
+                .saturating_add(p)
+                .saturating_add(c.p)
+                .saturating_add(min(q, c.q)),
+        )
+    }
+}
+
+pub fn carlier(tasks: Vec<Task>) -> (Vec<Task>, u32) {
+    let mut state = Carlier::new(tasks.clone(), u32::MAX, 0);
+    state.solve();
+
+    let order: Vec<Task> = state.best.iter().map(|&idx| tasks[idx]).collect();
+    let cmax = cmax(&order);
+    (order, cmax)
+}
+
+pub fn carlier_cmax(tasks: Vec<Task>) -> u32 {
+    carlier(tasks).1
+}
+
+// decision version, an order with cmax at most target or None when there is none
+// branches that can not reach the target are cut right away
+pub fn carlier_decision(tasks: Vec<Task>, target: u32) -> Option<Vec<Task>> {
+    let mut state = Carlier::new(tasks.clone(), target.saturating_add(1), target);
+    state.solve();
+
+    (state.upper_bound <= target).then(|| state.best.iter().map(|&idx| tasks[idx]).collect())
+}
+
+// levels of the tree whose branches are split between rayon tasks,
+// below them every subtree is searched sequentially from the best bound known when it starts
+#[cfg(feature = "parallel")]
+const PARALLEL_DEPTH: usize = 8;
+
+// best schedule found by any of the parallel searches
+#[cfg(feature = "parallel")]
+struct SharedBest {
+    upper_bound: AtomicU32,
+    best: Mutex<Vec<usize>>,
+}
+
+#[cfg(feature = "parallel")]
+impl SharedBest {
+    fn upper_bound(&self) -> u32 {
+        self.upper_bound.load(Ordering::Relaxed)
+    }
+
+    // the bound of a state only drops below the shared one when it found a better schedule
+    fn offer(&self, state: &Carlier) {
+        if state.upper_bound >= self.upper_bound() {
+            return;
+        }
+        let mut best = self.best.lock().unwrap();
+        if state.upper_bound < self.upper_bound() {
+            self.upper_bound.store(state.upper_bound, Ordering::Relaxed);
+            *best = state.best.clone();
+        }
+    }
+}
+
+#[cfg(feature = "parallel")]
+fn solve_parallel(mut state: Carlier, shared: &SharedBest, depth: usize) {
+    state.upper_bound = shared.upper_bound();
+    if depth == 0 {
+        state.solve();
+        shared.offer(&state);
+        return;
+    }
+    let split = state.split();
+    shared.offer(&state);
+    let Some(split) = split else {
+        return;
+    };
+
+    // every branch gets its own copy of the instance
+    let c_idx = split.c_idx;
+    let interference = state.tasks[c_idx];
+    let mut after = Carlier::new(state.tasks.clone(), state.upper_bound, state.target);
+    after.tasks[c_idx] = split.after_block(interference);
+    state.tasks[c_idx] = split.before_block(interference);
+    rayon::join(
+        || branch_parallel(after, &split, shared, depth - 1),
+        || branch_parallel(state, &split, shared, depth - 1),
+    );
+}
+
+#[cfg(feature = "parallel")]
+fn branch_parallel(mut state: Carlier, split: &Split, shared: &SharedBest, depth: usize) {
+    if state.lower_bound(split) < shared.upper_bound() {
+        solve_parallel(state, shared, depth);
+    }
+}
+
+// carlier with the branches near the root searched on the rayon pool,
+// the cmax is the same as of carlier but the order may differ
+#[cfg(feature = "parallel")]
+pub fn carlier_parallel(tasks: Vec<Task>) -> (Vec<Task>, u32) {
+    let shared = SharedBest {
+        upper_bound: AtomicU32::new(u32::MAX),
+        best: Mutex::new(Vec::new()),
+    };
+    let state = Carlier::new(tasks.clone(), u32::MAX, 0);
+    solve_parallel(state, &shared, PARALLEL_DEPTH);
+
+    let best = shared.best.into_inner().unwrap();
+    let order: Vec<Task> = best.iter().map(|&idx| tasks[idx]).collect();
+    let cmax = cmax(&order);
+    (order, cmax)
+}
+
+#[cfg(feature = "parallel")]
+pub fn carlier_parallel_cmax(tasks: Vec<Task>) -> u32 {
+    carlier_parallel(tasks).1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! tasks {
+        () => {
+            vec![
+                Task::new(30, 3, 8),
+                Task::new(20, 4, 21),
+                Task::new(10, 5, 7),
+                Task::new(11, 7, 24),
+                Task::new(30, 2, 0),
+                Task::new(13, 6, 26),
+                Task::new(0, 6, 17),
+            ]
+        };
+    }
+
+    // checks every permutation, only for tiny instances
+    fn brute_force_cmax(tasks: &mut Vec<Task>, k: usize) -> u32 {
+        if k == tasks.len() {
+            return cmax(tasks);
+        }
+        let mut best = u32::MAX;
+        for i in k..tasks.len() {
+            tasks.swap(k, i);
+            best = min(best, brute_force_cmax(tasks, k + 1));
+            tasks.swap(k, i);
+        }
+        best
+    }
+
+    #[test]
+    fn test_carlier() {
+        let (order, cmax) = carlier(tasks!());
+        assert_eq!(cmax, brute_force_cmax(&mut tasks!(), 0));
+        assert_eq!(cmax, 50);
+        let mut sorted = order.clone();
+        sorted.sort();
+        let mut expected = tasks!();
+        expected.sort();
+        assert_eq!(sorted, expected);
+    }
+
+    #[test]
+    fn test_carlier_decision() {
+        let order = carlier_decision(tasks!(), 52).unwrap();
+        assert!(cmax(&order) <= 52);
+        assert!(carlier_decision(tasks!(), 50).is_some());
+        assert_eq!(carlier_decision(tasks!(), 49), None);
+        assert_eq!(carlier_decision(vec![], 0), Some(vec![]));
+    }
+
+    #[test]
+    fn test_carlier_empty() {
+        assert_eq!(carlier(vec![]), (vec![], 0));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_carlier_parallel() {
+        let (order, cmax) = carlier_parallel(tasks!());
+        assert_eq!(cmax, 50);
+        let mut sorted = order.clone();
+        sorted.sort();
+        let mut expected = tasks!();
+        expected.sort();
+        assert_eq!(sorted, expected);
+        assert_eq!(carlier_parallel(vec![]), (vec![], 0));
+
+        let mut generator = crate::generator::Generator::new(11);
+        for n in [5, 20, 50] {
+            let tasks = generator.uniform(n, &(0..200), &(1..50), &(0..200));
+            assert_eq!(carlier_parallel_cmax(tasks.clone()), carlier_cmax(tasks));
+        }
+    }
+
+    #[test]
+    fn test_carlier_duplicates() {
+        let mut tasks = vec![Task::new(0, 5, 10); 3];
+        tasks.push(Task::new(1, 10, 30));
+        assert_eq!(carlier_cmax(tasks.clone()), brute_force_cmax(&mut tasks, 0));
+    }
+
+    #[test]
+    fn test_block_bound_saturates() {
+        let split = Split {
+            c_idx: 0,
+            r: u32::MAX - 1,
+            p: 5,
+            q: 3,
+            eliminated: vec![],
+        };
+        assert_eq!(split.block_bound(Task::new(0, 1, 0)), u32::MAX);
+        assert_eq!(split.after_block(Task::new(0, 1, 0)).r, u32::MAX);
+    }
+}
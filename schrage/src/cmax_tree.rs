@@ -0,0 +1,235 @@
+use crate::task::Task;
+use alloc::{vec, vec::Vec};
+use core::cmp::max;
+
+// tasks executed one after another starting at time t:
+// they finish at max(t + a, b) and give cmax of max(t + qa, qb)
+#[derive(Debug, Default, Clone, Copy)]
+struct Segment {
+    a: u32,
+    b: u32,
+    qa: u32,
+    qb: u32,
+}
+
+impl Segment {
+    fn leaf(task: &Task) -> Segment {
+        Segment {
+            a: task.p,
+            b: task.r + task.p,
+            qa: task.p + task.q,
+            qb: task.r + task.p + task.q,
+        }
+    }
+
+    // self executed first, then next
+    fn then(self, next: Segment) -> Segment {
+        Segment {
+            a: self.a + next.a,
+            b: max(self.b + next.a, next.b),
+            qa: max(self.qa, self.a + next.qa),
+            qb: max(self.qb, max(self.b + next.qa, next.qb)),
+        }
+    }
+
+    fn cmax(self) -> u32 {
+        max(self.qa, self.qb)
+    }
+}
+
+// segment tree over an order of tasks, cmax after a swap or an insertion
+// is evaluated in O(log n) without touching the order
+#[derive(Debug, Clone)]
+pub struct CmaxTree {
+    order: Vec<Task>,
+    size: usize,
+    tree: Vec<Segment>,
+}
+
+impl CmaxTree {
+    pub fn new(order: Vec<Task>) -> CmaxTree {
+        let size = order.len().next_power_of_two();
+        let mut tree = CmaxTree {
+            order,
+            size,
+            tree: vec![Segment::default(); 2 * size],
+        };
+        tree.rebuild();
+        tree
+    }
+
+    fn rebuild(&mut self) {
+        for (idx, task) in self.order.iter().enumerate() {
+            self.tree[self.size + idx] = Segment::leaf(task);
+        }
+        for node in (1..self.size).rev() {
+            self.tree[node] = self.tree[2 * node].then(self.tree[2 * node + 1]);
+        }
+    }
+
+    fn update(&mut self, idx: usize) {
+        let mut node = self.size + idx;
+        self.tree[node] = Segment::leaf(&self.order[idx]);
+        while node > 1 {
+            node /= 2;
+            self.tree[node] = self.tree[2 * node].then(self.tree[2 * node + 1]);
+        }
+    }
+
+    // composition of positions from..to (exclusive)
+    fn query(&self, from: usize, to: usize) -> Segment {
+        let mut left = Segment::default();
+        let mut right = Segment::default();
+        let mut l = from + self.size;
+        let mut r = to + self.size;
+        while l < r {
+            if l % 2 == 1 {
+                left = left.then(self.tree[l]);
+                l += 1;
+            }
+            if r % 2 == 1 {
+                r -= 1;
+                right = self.tree[r].then(right);
+            }
+            l /= 2;
+            r /= 2;
+        }
+        left.then(right)
+    }
+
+    pub fn order(&self) -> &[Task] {
+        &self.order
+    }
+
+    pub fn into_order(self) -> Vec<Task> {
+        self.order
+    }
+
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    pub fn cmax(&self) -> u32 {
+        self.tree[1].cmax()
+    }
+
+    pub fn cmax_with_swap(&self, i: usize, j: usize) -> u32 {
+        let (i, j) = if i < j { (i, j) } else { (j, i) };
+        if i == j {
+            return self.cmax();
+        }
+        self.query(0, i)
+            .then(Segment::leaf(&self.order[j]))
+            .then(self.query(i + 1, j))
+            .then(Segment::leaf(&self.order[i]))
+            .then(self.query(j + 1, self.len()))
+            .cmax()
+    }
+
+    // the task from position from ends up at position to
+    pub fn cmax_with_insertion(&self, from: usize, to: usize) -> u32 {
+        let moved = Segment::leaf(&self.order[from]);
+        if from < to {
+            self.query(0, from)
+                .then(self.query(from + 1, to + 1))
+                .then(moved)
+                .then(self.query(to + 1, self.len()))
+                .cmax()
+        } else {
+            self.query(0, to)
+                .then(moved)
+                .then(self.query(to, from))
+                .then(self.query(from + 1, self.len()))
+                .cmax()
+        }
+    }
+
+    pub fn swap(&mut self, i: usize, j: usize) {
+        self.order.swap(i, j);
+        self.update(i);
+        self.update(j);
+    }
+
+    pub fn insert(&mut self, from: usize, to: usize) {
+        if from < to {
+            self.order[from..=to].rotate_left(1);
+        } else {
+            self.order[to..=from].rotate_right(1);
+        }
+        for idx in from.min(to)..=from.max(to) {
+            self.update(idx);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schedule::cmax;
+
+    macro_rules! tasks {
+        () => {
+            vec![
+                Task::new(30, 3, 8),
+                Task::new(20, 4, 21),
+                Task::new(10, 5, 7),
+                Task::new(11, 7, 24),
+                Task::new(30, 2, 0),
+                Task::new(13, 6, 26),
+                Task::new(0, 6, 17),
+            ]
+        };
+    }
+
+    #[test]
+    fn test_cmax() {
+        let tasks = tasks!();
+        let tree = CmaxTree::new(tasks.clone());
+        assert_eq!(tree.cmax(), cmax(&tasks));
+        assert_eq!(CmaxTree::new(vec![]).cmax(), 0);
+    }
+
+    #[test]
+    fn test_cmax_with_swap() {
+        let tree = CmaxTree::new(tasks!());
+        for i in 0..tree.len() {
+            for j in 0..tree.len() {
+                let mut order = tasks!();
+                order.swap(i, j);
+                assert_eq!(tree.cmax_with_swap(i, j), cmax(&order));
+            }
+        }
+    }
+
+    #[test]
+    fn test_cmax_with_insertion() {
+        let tree = CmaxTree::new(tasks!());
+        for from in 0..tree.len() {
+            for to in 0..tree.len() {
+                let mut order = tasks!();
+                let task = order.remove(from);
+                order.insert(to, task);
+                assert_eq!(tree.cmax_with_insertion(from, to), cmax(&order));
+
+                let mut moved = tree.clone();
+                moved.insert(from, to);
+                assert_eq!(moved.order(), &order[..]);
+                assert_eq!(moved.cmax(), cmax(&order));
+            }
+        }
+    }
+
+    #[test]
+    fn test_swap() {
+        let mut tree = CmaxTree::new(tasks!());
+        tree.swap(1, 5);
+        let mut order = tasks!();
+        order.swap(1, 5);
+        assert_eq!(tree.order(), &order[..]);
+        assert_eq!(tree.cmax(), cmax(&order));
+    }
+}
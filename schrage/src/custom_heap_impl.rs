@@ -1,104 +1,153 @@
+use crate::std_heap_impl::{schrage_loop, schrage_preemptive_loop};
 use crate::task::{QInvariant, RInvariant, Task};
-use heap::HeapTree;
-use std::cmp::max;
+use alloc::vec::Vec;
+use heap::{CapacityError, Heap, HeapTree};
 
 pub fn schrage_custom_heaps(tasks: Vec<Task>) -> (Vec<Task>, u32) {
-    let mut available_tasks: HeapTree<QInvariant> = HeapTree::new();
-    let mut unavailable_tasks: HeapTree<RInvariant> = tasks.iter().map(|t| t.into()).collect();
-    let mut t = 0;
-    let mut cmax = 0;
-    let mut order = Vec::new();
-
-    // heaps make code cleaner and more imperative
-    while !available_tasks.is_empty() || !unavailable_tasks.is_empty() {
-        // unwrap is safe beacause the while loop condition
-        while !unavailable_tasks.is_empty() && unavailable_tasks.peek().unwrap().0.r <= t {
-            let task: Task = unavailable_tasks.pop().unwrap().into();
-            available_tasks.push(task.into());
-        }
-        if available_tasks.is_empty() {
-            // unwrap is safe, if the available_tasks is empty
-            // then the unavailable_tasks is not empty
-            // because of the while loop condition
-            t = unavailable_tasks.peek().unwrap().0.r;
-            continue;
+    schrage_custom_heaps_slice(&tasks)
+}
+
+// the two heaps of schrage kept between runs, like std_heap_impl::SchrageScratch
+#[derive(Debug, Default)]
+pub struct CustomHeapsScratch {
+    available_tasks: HeapTree<QInvariant>,
+    unavailable_tasks: HeapTree<RInvariant>,
+}
+
+impl CustomHeapsScratch {
+    pub fn new() -> CustomHeapsScratch {
+        CustomHeapsScratch::default()
+    }
+
+    pub fn with_capacity(n: usize) -> CustomHeapsScratch {
+        CustomHeapsScratch {
+            available_tasks: HeapTree::with_capacity(n),
+            unavailable_tasks: HeapTree::with_capacity(n),
         }
+    }
+
+    // the order is written into the buffer, previous contents are dropped
+    pub fn schrage_into(&mut self, tasks: &[Task], order: &mut Vec<Task>) -> u32 {
+        order.clear();
+        schrage_generic_heaps(
+            tasks,
+            &mut self.available_tasks,
+            &mut self.unavailable_tasks,
+            |task| order.push(task),
+        )
+        .unwrap_or_else(|_| unreachable!("heap trees grow"))
+    }
+
+    // just cmax
+    pub fn schrage_cmax(&mut self, tasks: &[Task]) -> u32 {
+        schrage_generic_heaps_cmax(
+            tasks,
+            &mut self.available_tasks,
+            &mut self.unavailable_tasks,
+        )
+        .unwrap_or_else(|_| unreachable!("heap trees grow"))
+    }
 
-        let task = available_tasks.pop().unwrap().0;
-        t += task.p;
-        cmax = max(cmax, t + task.q);
-        order.push(task);
+    pub fn schrage_preemptive_cmax(&mut self, tasks: &[Task]) -> u32 {
+        schrage_preemptive_generic_heaps_cmax(
+            tasks,
+            &mut self.available_tasks,
+            &mut self.unavailable_tasks,
+        )
+        .unwrap_or_else(|_| unreachable!("heap trees grow"))
     }
+}
 
+// entry points that borrow the instance
+pub fn schrage_custom_heaps_slice(tasks: &[Task]) -> (Vec<Task>, u32) {
+    let mut order = Vec::with_capacity(tasks.len());
+    let cmax = schrage_custom_heaps_into(tasks, &mut order);
     (order, cmax)
 }
 
-// just cmax
-pub fn schrage_custom_heaps_cmax(tasks: Vec<Task>) -> u32 {
-    let mut available_tasks: HeapTree<QInvariant> = HeapTree::new();
-    let mut unavailable_tasks: HeapTree<RInvariant> = tasks.iter().map(|t| t.into()).collect();
-    let mut t = 0;
-    let mut cmax = 0;
-
-    while !available_tasks.is_empty() || !unavailable_tasks.is_empty() {
-        while !unavailable_tasks.is_empty() && unavailable_tasks.peek().unwrap().0.r <= t {
-            let task: Task = unavailable_tasks.pop().unwrap().into();
-            available_tasks.push(task.into());
-        }
+pub fn schrage_custom_heaps_into(tasks: &[Task], order: &mut Vec<Task>) -> u32 {
+    CustomHeapsScratch::with_capacity(tasks.len()).schrage_into(tasks, order)
+}
 
-        if available_tasks.is_empty() {
-            t = unavailable_tasks.peek().unwrap().0.r;
-            continue;
-        }
+pub fn schrage_custom_heaps_slice_cmax(tasks: &[Task]) -> u32 {
+    CustomHeapsScratch::with_capacity(tasks.len()).schrage_cmax(tasks)
+}
 
-        let task = available_tasks.pop().unwrap().0;
-        t += task.p;
-        cmax = max(cmax, t + task.q);
-    }
+pub fn schrage_preemptive_custom_heaps_slice_cmax(tasks: &[Task]) -> u32 {
+    CustomHeapsScratch::with_capacity(tasks.len()).schrage_preemptive_cmax(tasks)
+}
 
-    cmax
+// just cmax
+pub fn schrage_custom_heaps_cmax(tasks: Vec<Task>) -> u32 {
+    schrage_custom_heaps_slice_cmax(&tasks)
 }
 
 pub fn schrage_preemptive_custom_heaps_cmax(tasks: Vec<Task>) -> u32 {
-    let mut available_tasks: HeapTree<QInvariant> = HeapTree::new();
-    let mut unavailable_tasks: HeapTree<RInvariant> = tasks.iter().map(|t| t.into()).collect();
-    let mut t = 0;
-    let mut cmax = 0;
-
-    let mut current_task: Option<Task> = None;
-
-    while !available_tasks.is_empty() || !unavailable_tasks.is_empty() {
-        while !unavailable_tasks.is_empty() && unavailable_tasks.peek().unwrap().0.r <= t {
-            let task: Task = unavailable_tasks.pop().unwrap().into();
-            available_tasks.push(task.into());
-            if let Some(mut current) = current_task {
-                if task.q > current.q {
-                    current.p = t - task.r;
-                    t = task.r;
-                    if current.p > 0 {
-                        available_tasks.push(current.into())
-                    }
-                }
-            }
-        }
+    schrage_preemptive_custom_heaps_slice_cmax(&tasks)
+}
 
-        if available_tasks.is_empty() {
-            t = unavailable_tasks.peek().unwrap().0.r;
-            continue;
-        }
+// the loop of schrage on any heap storage, every scheduled task is passed on to `scheduled`
+// with ArrayHeaps nothing is allocated and an instance that does not fit is an error
+pub fn schrage_generic_heaps<A, U>(
+    tasks: &[Task],
+    available_tasks: &mut A,
+    unavailable_tasks: &mut U,
+    scheduled: impl FnMut(Task),
+) -> Result<u32, CapacityError<Task>>
+where
+    A: Heap<QInvariant>,
+    U: Heap<RInvariant>,
+{
+    fill(tasks, available_tasks, unavailable_tasks)?;
+    schrage_loop(available_tasks, unavailable_tasks, QInvariant, scheduled)
+}
 
-        let task_to_do = available_tasks.pop().unwrap().0;
-        t += task_to_do.p;
-        cmax = max(cmax, t + task_to_do.q);
-        current_task = Some(task_to_do);
-    }
+pub fn schrage_generic_heaps_cmax<A, U>(
+    tasks: &[Task],
+    available_tasks: &mut A,
+    unavailable_tasks: &mut U,
+) -> Result<u32, CapacityError<Task>>
+where
+    A: Heap<QInvariant>,
+    U: Heap<RInvariant>,
+{
+    schrage_generic_heaps(tasks, available_tasks, unavailable_tasks, |_| {})
+}
+
+pub fn schrage_preemptive_generic_heaps_cmax<A, U>(
+    tasks: &[Task],
+    available_tasks: &mut A,
+    unavailable_tasks: &mut U,
+) -> Result<u32, CapacityError<Task>>
+where
+    A: Heap<QInvariant>,
+    U: Heap<RInvariant>,
+{
+    fill(tasks, available_tasks, unavailable_tasks)?;
+    schrage_preemptive_loop(available_tasks, unavailable_tasks, QInvariant)
+}
 
-    cmax
+// empties both heaps and puts every task in the unavailable one
+fn fill<A, U>(
+    tasks: &[Task],
+    available_tasks: &mut A,
+    unavailable_tasks: &mut U,
+) -> Result<(), CapacityError<Task>>
+where
+    A: Heap<QInvariant>,
+    U: Heap<RInvariant>,
+{
+    available_tasks.clear();
+    unavailable_tasks.clear();
+    unavailable_tasks
+        .push_all(tasks.iter().map(RInvariant::from))
+        .map_err(|e| CapacityError(e.0 .0))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use heap::ArrayHeap;
 
     macro_rules! tasks {
         () => {
@@ -128,6 +177,68 @@ mod tests {
         };
     }
 
+    #[test]
+    fn test_slices_and_scratch() {
+        let tasks = tasks!();
+        assert_eq!(schrage_custom_heaps_slice(&tasks), (correct_order!(), 53));
+        assert_eq!(schrage_preemptive_custom_heaps_slice_cmax(&tasks), 49);
+
+        let mut scratch = CustomHeapsScratch::new();
+        let mut order = Vec::new();
+        for _ in 0..2 {
+            assert_eq!(scratch.schrage_into(&tasks, &mut order), 53);
+            assert_eq!(order, correct_order!());
+            assert_eq!(scratch.schrage_cmax(&tasks), 53);
+            assert_eq!(scratch.schrage_preemptive_cmax(&tasks), 49);
+        }
+    }
+
+    #[test]
+    fn test_generic_heaps() {
+        let tasks = tasks!();
+        let mut available: ArrayHeap<QInvariant, 8> = ArrayHeap::new();
+        let mut unavailable: ArrayHeap<RInvariant, 8> = ArrayHeap::new();
+        let mut order = [Task::new(0, 0, 0); 7];
+        let mut scheduled = 0;
+        let cmax = schrage_generic_heaps(&tasks, &mut available, &mut unavailable, |task| {
+            order[scheduled] = task;
+            scheduled += 1;
+        });
+        assert_eq!(cmax, Ok(53));
+        assert_eq!(order.to_vec(), correct_order!());
+
+        // the heaps are reused and work the same when growable
+        assert_eq!(
+            schrage_generic_heaps_cmax(&tasks, &mut available, &mut unavailable),
+            Ok(53)
+        );
+        assert_eq!(
+            schrage_generic_heaps_cmax(&tasks, &mut HeapTree::new(), &mut HeapTree::new()),
+            Ok(53)
+        );
+        assert_eq!(
+            schrage_preemptive_generic_heaps_cmax(&tasks, &mut available, &mut unavailable),
+            Ok(49)
+        );
+    }
+
+    #[test]
+    fn test_generic_heaps_overflow() {
+        let tasks = tasks!();
+        let mut available: ArrayHeap<QInvariant, 8> = ArrayHeap::new();
+        let mut unavailable: ArrayHeap<RInvariant, 4> = ArrayHeap::new();
+        assert!(schrage_generic_heaps_cmax(&tasks, &mut available, &mut unavailable).is_err());
+
+        // every task is released at once so they all wait in the available heap
+        let tasks = vec![Task::new(0, 1, 1); 6];
+        let mut available: ArrayHeap<QInvariant, 4> = ArrayHeap::new();
+        let mut unavailable: ArrayHeap<RInvariant, 8> = ArrayHeap::new();
+        assert_eq!(
+            schrage_generic_heaps_cmax(&tasks, &mut available, &mut unavailable),
+            Err(CapacityError(Task::new(0, 1, 1)))
+        );
+    }
+
     #[test]
     fn test_schrage_heaps() {
         let tasks = tasks!();
@@ -152,6 +263,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::useless_conversion)]
     fn test_comparisons_qinvariant() {
         let mut heap: HeapTree<QInvariant> = tasks!().iter().map(|t| t.into()).collect();
         assert_eq!(heap.pop().unwrap().0, Task::new(13, 6, 26).into());
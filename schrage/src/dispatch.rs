@@ -0,0 +1,148 @@
+use crate::std_heap_impl::{largest_q, schrage_heaps_std_by_key};
+use crate::task::{Ratio, Task, WeightedTask};
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use {
+    crate::task::KeyInvariant,
+    alloc::collections::BinaryHeap,
+    core::cmp::{max, Reverse},
+};
+
+// weighted shortest processing time, largest w / p first
+pub fn wspt(tasks: Vec<WeightedTask>) -> (Vec<WeightedTask>, u32) {
+    schrage_heaps_std_by_key(tasks, |t| Ratio::new(t.w, t.task.p))
+}
+
+// earliest due date, with d = k - q this is the largest q that schrage uses
+pub fn edd(tasks: Vec<Task>) -> (Vec<Task>, u32) {
+    schrage_heaps_std_by_key(tasks, largest_q)
+}
+
+// apparent tardiness cost for due dates d = k - q, the index depends on the time
+// so the ready tasks are rescanned at every decision instead of being kept in a heap
+// exp is only available with std
+#[cfg(feature = "std")]
+pub fn atc(tasks: Vec<WeightedTask>, k: u32, lookahead: f64) -> (Vec<WeightedTask>, u32) {
+    let mean_p = tasks.iter().map(|t| t.task.p as f64).sum::<f64>() / tasks.len().max(1) as f64;
+    // only tasks without work, the slack is not scaled to avoid dividing by zero
+    let scale = if mean_p > 0.0 {
+        lookahead * mean_p
+    } else {
+        1.0
+    };
+    let index = |job: &WeightedTask, t: u32| {
+        let task = job.task;
+        let slack = (k as f64 - task.q as f64 - task.p as f64 - t as f64).max(0.0);
+        job.w as f64 / task.p as f64 * (-slack / scale).exp()
+    };
+
+    let mut available_tasks: Vec<WeightedTask> = Vec::new();
+    let mut unavailable_tasks: BinaryHeap<KeyInvariant<Reverse<u32>, WeightedTask>> = tasks
+        .iter()
+        .map(|&job| KeyInvariant(Reverse(job.task.r), job))
+        .collect();
+    let mut t = 0;
+    let mut cmax = 0;
+    let mut order = Vec::with_capacity(tasks.len());
+
+    while !available_tasks.is_empty() || !unavailable_tasks.is_empty() {
+        while !unavailable_tasks.is_empty() && unavailable_tasks.peek().unwrap().1.task.r <= t {
+            available_tasks.push(unavailable_tasks.pop().unwrap().1);
+        }
+        if available_tasks.is_empty() {
+            t = unavailable_tasks.peek().unwrap().1.task.r;
+            continue;
+        }
+
+        let best = (0..available_tasks.len())
+            .max_by(|&a, &b| {
+                index(&available_tasks[a], t).total_cmp(&index(&available_tasks[b], t))
+            })
+            .unwrap();
+        let job = available_tasks.swap_remove(best);
+        t += job.task.p;
+        cmax = max(cmax, t + job.task.q);
+        order.push(job);
+    }
+
+    (order, cmax)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objectives::weighted_completion_time;
+    use crate::std_heap_impl::schrage_heaps_std_cmax;
+
+    macro_rules! tasks {
+        () => {
+            vec![
+                Task::new(30, 3, 8),
+                Task::new(20, 4, 21),
+                Task::new(10, 5, 7),
+                Task::new(11, 7, 24),
+                Task::new(30, 2, 0),
+                Task::new(13, 6, 26),
+                Task::new(0, 6, 17),
+            ]
+        };
+    }
+
+    fn simultaneous() -> Vec<WeightedTask> {
+        vec![
+            WeightedTask::new(0, 4, 3, 1),
+            WeightedTask::new(0, 1, 9, 2),
+            WeightedTask::new(0, 3, 0, 5),
+            WeightedTask::new(0, 5, 7, 2),
+            WeightedTask::new(0, 2, 4, 1),
+        ]
+    }
+
+    #[test]
+    fn test_edd_is_schrage() {
+        assert_eq!(edd(tasks!()).1, schrage_heaps_std_cmax(tasks!()));
+    }
+
+    #[test]
+    fn test_wspt_optimal_without_ready_times() {
+        // smith's rule is optimal for the weighted completion time on one machine
+        let (order, _) = wspt(simultaneous());
+        assert_eq!(
+            order.iter().map(|t| t.task.p).collect::<Vec<_>>(),
+            vec![1, 3, 2, 5, 4]
+        );
+        assert_eq!(
+            weighted_completion_time(&order),
+            2 + 5 * 4 + 6 + 2 * 11 + 15
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_atc_limits() {
+        use crate::objectives::evaluate;
+
+        let k = 10;
+        // a long lookahead makes the slack irrelevant and atc turns into wspt
+        let (order, _) = atc(simultaneous(), k, 1e9);
+        assert_eq!(order, wspt(simultaneous()).0);
+        // the result is a permutation with a consistent cmax
+        let tasks: Vec<WeightedTask> = tasks!().into_iter().map(WeightedTask::from).collect();
+        let (order, cmax) = atc(tasks.clone(), 26, 2.0);
+        let mut sorted = order.clone();
+        sorted.sort();
+        let mut expected = tasks;
+        expected.sort();
+        assert_eq!(sorted, expected);
+        assert_eq!(evaluate(&order, 26).cmax, cmax);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_atc_zero_length_tasks() {
+        let tasks = vec![WeightedTask::new(0, 0, 4, 1), WeightedTask::new(2, 0, 1, 3)];
+        let (order, cmax) = atc(tasks.clone(), 4, 2.0);
+        assert_eq!(order, tasks);
+        assert_eq!(cmax, 4);
+    }
+}
@@ -0,0 +1,191 @@
+use crate::carlier::carlier_decision;
+use crate::std_heap_impl::schrage_preemptive_heaps_std_cmax;
+use crate::task::Task;
+use alloc::vec::Vec;
+use core::cmp::max;
+
+// task with a due date instead of a delivery time
+#[derive(Eq, PartialEq, PartialOrd, Ord, Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DueDateTask {
+    pub r: u32, // ready time
+    pub p: u32, // working time
+    pub d: u32, // due date
+}
+
+impl DueDateTask {
+    pub fn new(r: u32, p: u32, d: u32) -> DueDateTask {
+        DueDateTask { r, p, d }
+    }
+
+    // delivery time q = k - d, None when k is smaller than d
+    pub fn to_task(&self, k: u32) -> Option<Task> {
+        Some(Task::new(self.r, self.p, k.checked_sub(self.d)?))
+    }
+}
+
+// instance with delivery times and the k used, the largest due date,
+// for every order cmax of the returned tasks equals lmax + k
+pub fn to_delivery_times(tasks: &[DueDateTask]) -> (Vec<Task>, u32) {
+    let k = tasks.iter().map(|t| t.d).max().unwrap_or(0);
+    // unwrap is safe, k is the largest due date
+    (tasks.iter().map(|t| t.to_task(k).unwrap()).collect(), k)
+}
+
+// the k used by to_due_dates, the largest delivery time
+pub fn due_date_horizon(tasks: &[Task]) -> u32 {
+    tasks.iter().map(|t| t.q).max().unwrap_or(0)
+}
+
+// instance with due dates and the k used, the largest delivery time
+pub fn to_due_dates(tasks: &[Task]) -> (Vec<DueDateTask>, u32) {
+    let k = due_date_horizon(tasks);
+    // unwrap is safe, k is the largest delivery time
+    (
+        tasks
+            .iter()
+            .map(|t| t.to_due_date_task(k).unwrap())
+            .collect(),
+        k,
+    )
+}
+
+// maximum lateness of tasks executed in the given order, negative when all are early,
+// None for an empty order
+pub fn lmax(order: &[DueDateTask]) -> Option<i64> {
+    let mut t = 0;
+    let mut lmax = None;
+    for task in order {
+        t = max(t, task.r) + task.p;
+        lmax = max(lmax, Some(t as i64 - task.d as i64));
+    }
+    lmax
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Feasibility {
+    // order in which every task is done before its deadline
+    Feasible(Vec<DueDateTask>),
+    // even with preemption some deadline is missed, no branching was needed
+    InfeasiblePreemptive,
+    // rejected by carlier
+    Infeasible,
+}
+
+// due dates treated as hard deadlines
+pub fn deadline_feasibility(tasks: Vec<DueDateTask>) -> Feasibility {
+    let (delivery, k) = to_delivery_times(&tasks);
+
+    // a deadline is met when cmax of the delivery instance is at most k
+    if schrage_preemptive_heaps_std_cmax(delivery.clone()) > k {
+        return Feasibility::InfeasiblePreemptive;
+    }
+
+    // unwrap is safe, delivery times of the order are k - d
+    match carlier_decision(delivery, k) {
+        Some(order) => Feasibility::Feasible(
+            order
+                .iter()
+                .map(|t| t.to_due_date_task(k).unwrap())
+                .collect(),
+        ),
+        None => Feasibility::Infeasible,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schedule::cmax;
+
+    macro_rules! tasks {
+        () => {
+            vec![
+                Task::new(30, 3, 8),
+                Task::new(20, 4, 21),
+                Task::new(10, 5, 7),
+                Task::new(11, 7, 24),
+                Task::new(30, 2, 0),
+                Task::new(13, 6, 26),
+                Task::new(0, 6, 17),
+            ]
+        };
+    }
+
+    #[test]
+    fn test_conversion() {
+        let tasks = tasks!();
+        let (due, k) = to_due_dates(&tasks);
+        assert_eq!(k, 26);
+        assert_eq!(due[0], DueDateTask::new(30, 3, 18));
+        let (back, k_back) = to_delivery_times(&due);
+        assert_eq!(k_back, 26);
+        assert_eq!(back, tasks!());
+    }
+
+    #[test]
+    fn test_conversion_below_k() {
+        assert_eq!(
+            DueDateTask::new(1, 2, 7).to_task(10),
+            Some(Task::new(1, 2, 3))
+        );
+        assert_eq!(DueDateTask::new(1, 2, 7).to_task(6), None);
+        assert_eq!(
+            Task::new(1, 2, 3).to_due_date_task(3),
+            Some(DueDateTask::new(1, 2, 0))
+        );
+        assert_eq!(Task::new(1, 2, 3).to_due_date_task(2), None);
+    }
+
+    #[test]
+    fn test_lmax_matches_cmax() {
+        let tasks = tasks!();
+        let (due, k) = to_due_dates(&tasks);
+        assert_eq!(lmax(&due).unwrap() + k as i64, cmax(&tasks) as i64);
+        assert_eq!(lmax(&[DueDateTask::new(0, 2, 10)]), Some(-8));
+        assert_eq!(lmax(&[]), None);
+    }
+
+    #[test]
+    fn test_feasible() {
+        let tasks = vec![
+            DueDateTask::new(0, 3, 11),
+            DueDateTask::new(1, 2, 3),
+            DueDateTask::new(4, 4, 9),
+        ];
+        // the only feasible order keeps the machine idle between 3 and 4:
+        // second 1..3, third 4..8, first 8..11
+        match deadline_feasibility(tasks) {
+            Feasibility::Feasible(order) => {
+                assert!(lmax(&order) <= Some(0));
+                assert_eq!(
+                    order,
+                    vec![
+                        DueDateTask::new(1, 2, 3),
+                        DueDateTask::new(4, 4, 9),
+                        DueDateTask::new(0, 3, 11),
+                    ]
+                );
+            }
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_infeasible_preemptive() {
+        let tasks = vec![DueDateTask::new(0, 5, 4), DueDateTask::new(0, 1, 10)];
+        assert_eq!(
+            deadline_feasibility(tasks),
+            Feasibility::InfeasiblePreemptive
+        );
+    }
+
+    #[test]
+    fn test_infeasible_carlier() {
+        // with preemption the long task can be split around the short one,
+        // without it one of the deadlines is missed
+        let tasks = vec![DueDateTask::new(0, 4, 5), DueDateTask::new(1, 1, 2)];
+        assert_eq!(deadline_feasibility(tasks), Feasibility::Infeasible);
+    }
+}
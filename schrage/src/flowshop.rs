@@ -0,0 +1,316 @@
+use alloc::{vec, vec::Vec};
+use core::cmp::{max, Reverse};
+
+// processing times of every job on every machine, p[job][machine]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlowShop {
+    p: Vec<Vec<u32>>,
+}
+
+impl FlowShop {
+    // None when the jobs have times for different numbers of machines
+    pub fn new(p: Vec<Vec<u32>>) -> Option<FlowShop> {
+        let machines = p.first().map_or(0, Vec::len);
+        p.iter()
+            .all(|job| job.len() == machines)
+            .then_some(FlowShop { p })
+    }
+
+    pub fn p(&self) -> &[Vec<u32>] {
+        &self.p
+    }
+
+    pub fn jobs(&self) -> usize {
+        self.p.len()
+    }
+
+    pub fn machines(&self) -> usize {
+        self.p.first().map_or(0, |job| job.len())
+    }
+}
+
+// instance from a taillard benchmark file with its header values
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaillardInstance {
+    pub flow_shop: FlowShop,
+    pub seed: u64,
+    pub upper_bound: u32,
+    pub lower_bound: u32,
+}
+
+// taillard format, a file can hold several instances:
+// a header line, a line with jobs, machines, seed, upper and lower bound,
+// a "processing times" line and then one line of times per machine
+pub fn parse_taillard(input: &str) -> Option<Vec<TaillardInstance>> {
+    let mut instances = Vec::new();
+    let mut lines = input.lines().map(str::trim).filter(|l| !l.is_empty());
+
+    while let Some(line) = lines.next() {
+        if !line.starts_with("number of jobs") {
+            continue;
+        }
+        let header: Vec<u64> = lines
+            .next()?
+            .split_whitespace()
+            .map(|w| w.parse().ok())
+            .collect::<Option<_>>()?;
+        let [jobs, machines, seed, upper_bound, lower_bound] = header[..] else {
+            return None;
+        };
+        if !lines.next()?.starts_with("processing times") {
+            return None;
+        }
+
+        let mut p = vec![Vec::with_capacity(machines as usize); jobs as usize];
+        for _ in 0..machines {
+            let times: Vec<u32> = lines
+                .next()?
+                .split_whitespace()
+                .map(|w| w.parse().ok())
+                .collect::<Option<_>>()?;
+            if times.len() != jobs as usize {
+                return None;
+            }
+            for (job, time) in times.into_iter().enumerate() {
+                p[job].push(time);
+            }
+        }
+
+        instances.push(TaillardInstance {
+            flow_shop: FlowShop { p },
+            seed,
+            upper_bound: upper_bound as u32,
+            lower_bound: lower_bound as u32,
+        });
+    }
+
+    Some(instances)
+}
+
+// order of the jobs on every machine together with its cmax
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FlowShopSchedule {
+    pub order: Vec<usize>,
+    pub cmax: u32,
+}
+
+// permutation schedule, every machine processes the jobs in the same order
+pub fn flow_shop_cmax(instance: &FlowShop, order: &[usize]) -> u32 {
+    let mut completion = vec![0; instance.machines()];
+    for &job in order {
+        let mut t = 0;
+        for (machine, c) in completion.iter_mut().enumerate() {
+            t = max(t, *c) + instance.p[job][machine];
+            *c = t;
+        }
+    }
+    completion.last().copied().unwrap_or(0)
+}
+
+// johnson's rule, optimal for two machines:
+// jobs faster on the first machine go first by increasing p1,
+// the rest go last by decreasing p2
+pub fn johnson(instance: &FlowShop) -> Option<FlowShopSchedule> {
+    if instance.machines() != 2 {
+        return None;
+    }
+    let p = &instance.p;
+    let (mut first, mut last): (Vec<usize>, Vec<usize>) =
+        (0..instance.jobs()).partition(|&j| p[j][0] < p[j][1]);
+    first.sort_by_key(|&j| p[j][0]);
+    last.sort_by_key(|&j| Reverse(p[j][1]));
+    first.extend(last);
+
+    let cmax = flow_shop_cmax(instance, &first);
+    Some(FlowShopSchedule { order: first, cmax })
+}
+
+pub fn johnson_cmax(instance: &FlowShop) -> Option<u32> {
+    johnson(instance).map(|schedule| schedule.cmax)
+}
+
+// neh, jobs by decreasing total processing time are inserted at their best position,
+// every insertion step is evaluated in O(nm) with taillard's acceleration
+pub fn neh(instance: &FlowShop) -> FlowShopSchedule {
+    let m = instance.machines();
+    let mut jobs: Vec<usize> = (0..instance.jobs()).collect();
+    jobs.sort_by_key(|&j| Reverse(instance.p[j].iter().sum::<u32>()));
+
+    let mut order: Vec<usize> = Vec::with_capacity(jobs.len());
+    // heads e, tails q and completions f of the inserted job, zero borders around the times
+    let mut e = vec![vec![0u32; m + 2]; jobs.len() + 2];
+    let mut q = vec![vec![0u32; m + 2]; jobs.len() + 2];
+    let mut f = vec![0u32; m + 2];
+
+    for job in jobs {
+        let k = order.len();
+        let p = |j: usize, machine: usize| instance.p[j][machine - 1];
+
+        for i in 1..=k {
+            for machine in 1..=m {
+                e[i][machine] =
+                    max(e[i - 1][machine], e[i][machine - 1]) + p(order[i - 1], machine);
+            }
+        }
+        for i in (1..=k).rev() {
+            for machine in (1..=m).rev() {
+                q[i][machine] =
+                    max(q[i + 1][machine], q[i][machine + 1]) + p(order[i - 1], machine);
+            }
+        }
+
+        let mut best = (0, u32::MAX);
+        for position in 0..=k {
+            let mut cmax = 0;
+            for machine in 1..=m {
+                f[machine] = max(f[machine - 1], e[position][machine]) + p(job, machine);
+                cmax = max(cmax, f[machine] + q[position + 1][machine]);
+            }
+            if cmax < best.1 {
+                best = (position, cmax);
+            }
+        }
+        order.insert(best.0, job);
+    }
+
+    let cmax = flow_shop_cmax(instance, &order);
+    FlowShopSchedule { order, cmax }
+}
+
+pub fn neh_cmax(instance: &FlowShop) -> u32 {
+    neh(instance).cmax
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // checks every permutation, only for tiny instances
+    fn brute_force_cmax(instance: &FlowShop, order: &mut Vec<usize>, k: usize) -> u32 {
+        if k == order.len() {
+            return flow_shop_cmax(instance, order);
+        }
+        let mut best = u32::MAX;
+        for i in k..order.len() {
+            order.swap(k, i);
+            best = best.min(brute_force_cmax(instance, order, k + 1));
+            order.swap(k, i);
+        }
+        best
+    }
+
+    const TAILLARD: &str = "
+number of jobs, number of machines, initial seed, upper bound and lower bound :
+           5           3   123456789          26          24
+processing times :
+  5  9  8 10  1
+  7  3  5  2  6
+  3  4  8  6  5
+number of jobs, number of machines, initial seed, upper bound and lower bound :
+           6           2   987654321          43          40
+processing times :
+  3  5  1  6  7  9
+  6  2  2  6  5  4
+";
+
+    #[test]
+    fn test_parse_taillard() {
+        let instances = parse_taillard(TAILLARD).unwrap();
+        assert_eq!(instances.len(), 2);
+        assert_eq!(instances[0].flow_shop.jobs(), 5);
+        assert_eq!(instances[0].flow_shop.machines(), 3);
+        assert_eq!(instances[0].flow_shop.p[1], vec![9, 3, 4]);
+        assert_eq!(instances[0].seed, 123456789);
+        assert_eq!(instances[1].upper_bound, 43);
+        assert_eq!(instances[1].lower_bound, 40);
+        assert_eq!(parse_taillard("number of jobs\n1 1 1 1\n"), None);
+    }
+
+    #[test]
+    fn test_flow_shop_cmax() {
+        let instance = FlowShop::new(vec![vec![2, 3], vec![4, 1]]).unwrap();
+        assert_eq!(flow_shop_cmax(&instance, &[0, 1]), 7);
+        assert_eq!(flow_shop_cmax(&instance, &[1, 0]), 9);
+        assert_eq!(flow_shop_cmax(&instance, &[]), 0);
+    }
+
+    #[test]
+    fn test_johnson() {
+        let instance = parse_taillard(TAILLARD).unwrap().remove(1).flow_shop;
+        let schedule = johnson(&instance).unwrap();
+        assert_eq!(schedule.order, vec![2, 0, 3, 4, 5, 1]);
+        assert_eq!(
+            schedule.cmax,
+            brute_force_cmax(&instance, &mut (0..6).collect(), 0)
+        );
+        assert_eq!(
+            johnson_cmax(&parse_taillard(TAILLARD).unwrap()[0].flow_shop),
+            None
+        );
+    }
+
+    #[test]
+    fn test_neh() {
+        for instance in parse_taillard(TAILLARD).unwrap() {
+            let instance = instance.flow_shop;
+            let FlowShopSchedule { order, cmax } = neh(&instance);
+            assert_eq!(cmax, flow_shop_cmax(&instance, &order));
+            let mut sorted = order.clone();
+            sorted.sort();
+            assert_eq!(sorted, (0..instance.jobs()).collect::<Vec<_>>());
+
+            let optimum = brute_force_cmax(&instance, &mut (0..instance.jobs()).collect(), 0);
+            assert!(cmax >= optimum);
+            assert!(cmax <= optimum + optimum / 10);
+        }
+    }
+
+    #[test]
+    fn test_neh_acceleration() {
+        let instance = FlowShop::new(
+            (0..12)
+                .map(|j| {
+                    (0..5)
+                        .map(|m| (j * 37 + m * 53 + j * m * 11) % 97 + 1)
+                        .collect()
+                })
+                .collect(),
+        )
+        .unwrap();
+        let mut jobs: Vec<usize> = (0..instance.jobs()).collect();
+        jobs.sort_by_key(|&j| Reverse(instance.p[j].iter().sum::<u32>()));
+
+        // insertion evaluated from scratch for every position
+        let mut order = Vec::new();
+        for job in jobs {
+            let position = (0..=order.len())
+                .min_by_key(|&position| {
+                    let mut candidate = order.clone();
+                    candidate.insert(position, job);
+                    flow_shop_cmax(&instance, &candidate)
+                })
+                .unwrap();
+            order.insert(position, job);
+        }
+
+        assert_eq!(neh(&instance).order, order);
+    }
+
+    #[test]
+    fn test_neh_empty() {
+        let instance = FlowShop::new(vec![]).unwrap();
+        assert_eq!(neh(&instance), FlowShopSchedule::default());
+    }
+
+    #[test]
+    fn test_ragged_instance() {
+        assert_eq!(FlowShop::new(vec![vec![1, 2], vec![3]]), None);
+        assert_eq!(
+            FlowShop::new(vec![vec![1, 2], vec![3, 4]])
+                .unwrap()
+                .machines(),
+            2
+        );
+    }
+}
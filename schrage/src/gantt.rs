@@ -0,0 +1,239 @@
+use crate::schedule::{start_times, Schedule};
+use crate::task::Task;
+use crate::validation::Segment;
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::cmp::max;
+use core::fmt::Write;
+
+const SVG_WIDTH: f64 = 800.0;
+const SVG_LEFT: f64 = 60.0;
+const SVG_ROW: f64 = 24.0;
+const SVG_AXIS: f64 = 30.0;
+
+// pieces of a non-preemptive order, the task index is the position in the order
+pub fn order_segments(order: &[Task]) -> Vec<Segment> {
+    order
+        .iter()
+        .zip(start_times(order))
+        .enumerate()
+        .map(|(j, (task, start))| Segment::new(j, start, start + task.p))
+        .collect()
+}
+
+// pieces of the given tasks, the ones with an unknown task index are not drawn
+fn drawn<'a>(tasks: &[Task], segments: &'a [Segment]) -> impl Iterator<Item = &'a Segment> {
+    let n = tasks.len();
+    segments.iter().filter(move |s| s.task < n)
+}
+
+// completion time of every task, none for tasks without a piece
+fn completions(tasks: &[Task], segments: &[Segment]) -> Vec<Option<u32>> {
+    let mut completion = vec![None; tasks.len()];
+    for segment in drawn(tasks, segments) {
+        completion[segment.task] = max(completion[segment.task], Some(segment.end));
+    }
+    completion
+}
+
+// the last moment drawn, the end of the longest tail
+fn horizon(tasks: &[Task], completion: &[Option<u32>]) -> u32 {
+    tasks
+        .iter()
+        .zip(completion)
+        .map(|(task, c)| max(task.r, c.unwrap_or(0) + task.q))
+        .max()
+        .unwrap_or(0)
+        .max(1)
+}
+
+// one row per task, '#' processing, '-' the q tail after completion
+// and '>' the ready time when nothing else is drawn there
+pub fn render_ascii(tasks: &[Task], segments: &[Segment], width: usize) -> String {
+    let width = width.max(1);
+    let completion = completions(tasks, segments);
+    let horizon = horizon(tasks, &completion);
+    let column = |t: u32| (t as usize * width / horizon as usize).min(width);
+    let label = tasks.len().saturating_sub(1).to_string().len();
+
+    let mut rows = vec![vec![' '; width]; tasks.len()];
+    for (j, task) in tasks.iter().enumerate() {
+        if let Some(c) = completion[j] {
+            rows[j][column(c)..column(c + task.q)].fill('-');
+        }
+    }
+    for segment in drawn(tasks, segments).filter(|s| s.start < s.end) {
+        let from = column(segment.start).min(width - 1);
+        let to = max(column(segment.end), from + 1);
+        rows[segment.task][from..to].fill('#');
+    }
+
+    let cmax = tasks
+        .iter()
+        .zip(&completion)
+        .filter_map(|(task, c)| c.map(|c| c + task.q))
+        .max()
+        .unwrap_or(0);
+    let mut chart = format!("cmax {cmax}\n");
+    for (j, (task, mut row)) in tasks.iter().zip(rows).enumerate() {
+        let r = column(task.r).min(width - 1);
+        if row[r] == ' ' {
+            row[r] = '>';
+        }
+        let row: String = row.into_iter().collect();
+        let _ = writeln!(chart, "{j:>label$} |{row}| {task}");
+    }
+    let _ = writeln!(chart, "{:>label$} 0{:>width$}", "", horizon);
+    chart
+}
+
+// gantt chart with a row per task, the ready time is marked by a red line,
+// processing by blue bars and the q tail by a thin grey bar
+pub fn render_svg(tasks: &[Task], segments: &[Segment]) -> String {
+    let completion = completions(tasks, segments);
+    let horizon = horizon(tasks, &completion);
+    let scale = SVG_WIDTH / horizon as f64;
+    let x = |t: u32| SVG_LEFT + t as f64 * scale;
+    let height = tasks.len() as f64 * SVG_ROW + SVG_AXIS;
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" font-family="monospace" font-size="12">"#,
+        SVG_LEFT + SVG_WIDTH + 20.0,
+        height
+    );
+    for (j, task) in tasks.iter().enumerate() {
+        let y = j as f64 * SVG_ROW;
+        let _ = writeln!(svg, r#"<text x="4" y="{}">{j}</text>"#, y + SVG_ROW * 0.7);
+        let _ = writeln!(
+            svg,
+            r##"<line class="release" x1="{0}" y1="{1}" x2="{0}" y2="{2}" stroke="#c0392b" stroke-width="2"/>"##,
+            x(task.r),
+            y + 2.0,
+            y + SVG_ROW - 2.0
+        );
+        if let Some(c) = completion[j] {
+            let _ = writeln!(
+                svg,
+                r##"<rect class="tail" x="{}" y="{}" width="{}" height="{}" fill="#bdc3c7"/>"##,
+                x(c),
+                y + SVG_ROW * 0.4,
+                task.q as f64 * scale,
+                SVG_ROW * 0.2
+            );
+        }
+    }
+    for segment in drawn(tasks, segments).filter(|s| s.start < s.end) {
+        let _ = writeln!(
+            svg,
+            r##"<rect class="task" x="{}" y="{}" width="{}" height="{}" fill="#2980b9"><title>{} [{}, {})</title></rect>"##,
+            x(segment.start),
+            segment.task as f64 * SVG_ROW + 3.0,
+            (segment.end - segment.start) as f64 * scale,
+            SVG_ROW - 6.0,
+            segment.task,
+            segment.start,
+            segment.end
+        );
+    }
+
+    let axis = tasks.len() as f64 * SVG_ROW;
+    let _ = writeln!(
+        svg,
+        r#"<line x1="{}" y1="{axis}" x2="{}" y2="{axis}" stroke="black"/>"#,
+        x(0),
+        x(horizon)
+    );
+    for tick in 0..=10 {
+        let t = horizon * tick / 10;
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="{}" text-anchor="middle">{t}</text>"#,
+            x(t),
+            axis + 20.0
+        );
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+pub fn schedule_ascii(schedule: &Schedule, width: usize) -> String {
+    render_ascii(&schedule.order, &order_segments(&schedule.order), width)
+}
+
+pub fn schedule_svg(schedule: &Schedule) -> String {
+    render_svg(&schedule.order, &order_segments(&schedule.order))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::std_heap_impl::{schrage_heaps_std, schrage_preemptive_segments};
+
+    #[test]
+    fn test_order_segments() {
+        let order = vec![Task::new(0, 2, 1), Task::new(5, 3, 0)];
+        assert_eq!(
+            order_segments(&order),
+            vec![Segment::new(0, 0, 2), Segment::new(1, 5, 8)]
+        );
+    }
+
+    #[test]
+    fn test_ascii() {
+        let schedule = Schedule::from(vec![Task::new(0, 2, 4), Task::new(3, 3, 1)]);
+        assert_eq!(
+            schedule_ascii(&schedule, 7),
+            "cmax 7\n0 |##---- | (0, 2, 4)\n1 |   ###-| (3, 3, 1)\n  0      7\n"
+        );
+    }
+
+    #[test]
+    fn test_ascii_preemptive() {
+        let tasks = vec![Task::new(0, 4, 1), Task::new(1, 2, 10)];
+        let (segments, _) = schrage_preemptive_segments(&tasks);
+        let chart = render_ascii(&tasks, &segments, 13);
+        assert_eq!(
+            chart,
+            "cmax 13\n0 |#  ###-      | (0, 4, 1)\n1 | ##----------| (1, 2, 10)\n  0           13\n"
+        );
+    }
+
+    #[test]
+    fn test_svg() {
+        let tasks = vec![
+            Task::new(30, 3, 8),
+            Task::new(20, 4, 21),
+            Task::new(10, 5, 7),
+            Task::new(0, 6, 17),
+        ];
+        let schedule = Schedule::from(schrage_heaps_std(tasks.clone()));
+        let svg = schedule_svg(&schedule);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches(r#"class="task""#).count(), 4);
+        assert_eq!(svg.matches(r#"class="release""#).count(), 4);
+        assert_eq!(svg.matches(r#"class="tail""#).count(), 4);
+
+        let (segments, _) = schrage_preemptive_segments(&tasks);
+        let svg = render_svg(&tasks, &segments);
+        assert_eq!(svg.matches(r#"class="task""#).count(), segments.len());
+    }
+
+    #[test]
+    fn test_unknown_task_is_skipped() {
+        let tasks = vec![Task::new(0, 2, 4)];
+        let segments = vec![Segment::new(0, 0, 2), Segment::new(3, 2, 5)];
+        assert_eq!(
+            render_ascii(&tasks, &segments, 6),
+            "cmax 6\n0 |##----| (0, 2, 4)\n  0     6\n"
+        );
+        let svg = render_svg(&tasks, &segments);
+        assert_eq!(svg.matches(r#"class="task""#).count(), 1);
+    }
+}
@@ -0,0 +1,229 @@
+use crate::task::Task;
+use alloc::{borrow::ToOwned, vec::Vec};
+use core::ops::Range;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+// longest processing time drawn by the heavy tailed family
+#[cfg(feature = "std")]
+const MAX_HEAVY_P: u32 = 10_000;
+
+// n as a time bound, saturated when it does not fit in u32
+fn saturating_n(n: usize) -> u32 {
+    u32::try_from(n).unwrap_or(u32::MAX)
+}
+
+// upper bound of a range 1..=bound, at least 1 and with room for the + 1
+fn range_bound(bound: u32) -> u32 {
+    bound.clamp(1, u32::MAX - 1)
+}
+
+// instance families, the same seed always gives the same instances
+pub struct Generator {
+    rng: StdRng,
+}
+
+impl Generator {
+    pub fn new(seed: u64) -> Generator {
+        Generator {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn uniform(
+        &mut self,
+        amount: usize,
+        r_vals: &Range<u32>,
+        p_vals: &Range<u32>,
+        q_vals: &Range<u32>,
+    ) -> Vec<Task> {
+        (0..amount)
+            .map(|_| {
+                let r = self.rng.gen_range(r_vals.to_owned());
+                let p = self.rng.gen_range(p_vals.to_owned());
+                let q = self.rng.gen_range(q_vals.to_owned());
+                Task::new(r, p, q)
+            })
+            .collect()
+    }
+
+    // uniform instances of 10, 10 * (1 + step_by), ... up to 10 * multiplier tasks
+    pub fn uniform_sizes(
+        &mut self,
+        multiplier_higher_bound: u32,
+        step_by: usize,
+        bound_r: u32,
+        bound_p: u32,
+        bound_q: u32,
+    ) -> Vec<Vec<Task>> {
+        (1..=multiplier_higher_bound)
+            .step_by(step_by)
+            .map(|n| {
+                self.uniform(
+                    (10 * n) as usize,
+                    &(0..bound_r.max(1)),
+                    &(0..bound_p.max(1)),
+                    &(0..bound_q.max(1)),
+                )
+            })
+            .collect()
+    }
+
+    // carlier and grabowski: p in [1, 50], r and q in [1, n * k],
+    // the hardest instances are around k = 20, k = 0 is taken as r = q = 1
+    // and n * k is saturated at u32::MAX
+    pub fn carlier_grabowski(&mut self, n: usize, k: u32) -> Vec<Task> {
+        let bound = range_bound(saturating_n(n).saturating_mul(k));
+        self.uniform(n, &(1..bound + 1), &(1..51), &(1..bound + 1))
+    }
+
+    // ready times gathered around a few moments, within spread of them
+    pub fn clustered(&mut self, n: usize, clusters: usize, spread: u32) -> Vec<Task> {
+        let horizon = range_bound(saturating_n(n).saturating_mul(25));
+        let centers: Vec<u32> = (0..clusters.max(1))
+            .map(|_| self.rng.gen_range(0..horizon))
+            .collect();
+        (0..n)
+            .map(|_| {
+                let center = centers[self.rng.gen_range(0..centers.len())];
+                let r = center.saturating_add(self.rng.gen_range(0..=spread));
+                let p = self.rng.gen_range(1..51);
+                let q = self.rng.gen_range(1..horizon + 1);
+                Task::new(r, p, q)
+            })
+            .collect()
+    }
+
+    // pareto distributed processing times with the given shape, smaller alpha
+    // gives heavier tails, r and q are spread over the expected total work
+    // powf is only available with std
+    #[cfg(feature = "std")]
+    pub fn heavy_tailed(&mut self, n: usize, alpha: f64) -> Vec<Task> {
+        let p: Vec<u32> = (0..n)
+            .map(|_| {
+                let u: f64 = self.rng.gen_range(f64::EPSILON..1.0);
+                (u.powf(-1.0 / alpha).ceil() as u32).min(MAX_HEAVY_P)
+            })
+            .collect();
+        let horizon = p.iter().fold(0u32, |sum, &p| sum.saturating_add(p)).max(1);
+        p.into_iter()
+            .map(|p| {
+                let r = self.rng.gen_range(0..horizon);
+                let q = self.rng.gen_range(0..horizon);
+                Task::new(r, p, q)
+            })
+            .collect()
+    }
+
+    // one long task without cooldown is the only one ready at the start and
+    // urgent short tasks arrive right after it, schrage starts the long task
+    // and ends up close to twice the optimum
+    pub fn hard_for_schrage(&mut self, n: usize) -> Vec<Task> {
+        let long = saturating_n(n.max(1)).saturating_mul(50);
+        let mut tasks = Vec::with_capacity(n);
+        if n > 0 {
+            tasks.push(Task::new(0, long, 0));
+        }
+        for _ in 1..n {
+            let r = self.rng.gen_range(1..=saturating_n(n));
+            let p = self.rng.gen_range(1..6);
+            let q = self.rng.gen_range(long / 2..=long);
+            tasks.push(Task::new(r, p, q));
+        }
+        tasks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::std_heap_impl::{schrage_heaps_std_cmax, schrage_preemptive_heaps_std_cmax};
+
+    #[test]
+    fn test_seeded() {
+        assert_eq!(
+            Generator::new(3).carlier_grabowski(50, 20),
+            Generator::new(3).carlier_grabowski(50, 20)
+        );
+        assert_ne!(
+            Generator::new(3).carlier_grabowski(50, 20),
+            Generator::new(4).carlier_grabowski(50, 20)
+        );
+    }
+
+    #[test]
+    fn test_carlier_grabowski_bounds() {
+        let tasks = Generator::new(0).carlier_grabowski(100, 15);
+        assert_eq!(tasks.len(), 100);
+        for task in tasks {
+            assert!((1..=1500).contains(&task.r));
+            assert!((1..=50).contains(&task.p));
+            assert!((1..=1500).contains(&task.q));
+        }
+    }
+
+    #[test]
+    fn test_empty_ranges() {
+        let tasks = Generator::new(0).carlier_grabowski(5, 0);
+        assert_eq!(tasks.len(), 5);
+        assert!(tasks.iter().all(|t| t.r == 1 && t.q == 1));
+        assert!(Generator::new(0).carlier_grabowski(0, 20).is_empty());
+        assert!(Generator::new(0).clustered(0, 5, 20).is_empty());
+        let sets = Generator::new(0).uniform_sizes(1, 1, 0, 0, 0);
+        assert!(sets[0].iter().all(|&t| t == Task::new(0, 0, 0)));
+    }
+
+    #[test]
+    fn test_saturated_bounds() {
+        let tasks = Generator::new(0).carlier_grabowski(5, u32::MAX);
+        assert!(tasks.iter().all(|t| t.r >= 1 && t.q >= 1));
+        let tasks = Generator::new(0).clustered(10, 2, u32::MAX);
+        assert_eq!(tasks.len(), 10);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_heavy_tailed_saturated_horizon() {
+        // almost every p is MAX_HEAVY_P so the total work does not fit in u32
+        let tasks = Generator::new(0).heavy_tailed(500_000, 0.01);
+        assert!(tasks.iter().map(|t| t.p as u64).sum::<u64>() > u32::MAX as u64);
+    }
+
+    #[test]
+    fn test_uniform_sizes() {
+        let sets = Generator::new(0).uniform_sizes(30, 3, 1000, 1000, 1000);
+        let sizes: Vec<usize> = sets.iter().map(Vec::len).collect();
+        assert_eq!(sizes, vec![10, 40, 70, 100, 130, 160, 190, 220, 250, 280]);
+    }
+
+    #[test]
+    fn test_clustered() {
+        let tasks = Generator::new(1).clustered(200, 3, 10);
+        let mut releases: Vec<u32> = tasks.iter().map(|t| t.r).collect();
+        releases.sort();
+        releases.dedup();
+        // every release is within 10 of one of three centers
+        assert!(releases.len() <= 3 * 11);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_heavy_tailed() {
+        let tasks = Generator::new(2).heavy_tailed(1000, 1.2);
+        let max = tasks.iter().map(|t| t.p).max().unwrap();
+        let mut p: Vec<u32> = tasks.iter().map(|t| t.p).collect();
+        p.sort();
+        assert!(p[0] >= 1 && max <= MAX_HEAVY_P);
+        assert!(max > 20 * p[p.len() / 2]);
+    }
+
+    #[test]
+    fn test_hard_for_schrage() {
+        let tasks = Generator::new(5).hard_for_schrage(21);
+        assert_eq!(tasks.len(), 21);
+        // the preemptive bound is reached by waiting for the urgent task
+        let schrage = schrage_heaps_std_cmax(tasks.clone());
+        let bound = schrage_preemptive_heaps_std_cmax(tasks);
+        assert!(2 * schrage > 3 * bound);
+    }
+}
@@ -0,0 +1,148 @@
+use crate::schedule::{cmax, critical_path, order_indices};
+use crate::std_heap_impl::SchrageScratch;
+use crate::symmetry::reverse;
+use crate::task::Task;
+use alloc::{vec, vec::Vec};
+use core::cmp::max;
+
+// schrage is run at most n times, after every run the interference task
+// gets the ready time of the task that determines cmax so it is pushed behind it
+// returns the best order as indices into tasks and its cmax
+fn potts_indices(tasks: &[Task]) -> (Vec<usize>, u32) {
+    let mut modified = tasks.to_vec();
+    let mut best = (Vec::new(), u32::MAX);
+    let mut scratch = SchrageScratch::with_capacity(tasks.len());
+    let mut order = Vec::with_capacity(tasks.len());
+
+    for _ in 0..max(tasks.len(), 1) {
+        scratch.schrage_into(&modified, &mut order);
+        let indices = order_indices(&order, &modified);
+        // modifications only delay tasks so the order is evaluated on the original data
+        let cmax = cmax(&indices.iter().map(|&i| tasks[i]).collect::<Vec<_>>());
+        if cmax < best.1 {
+            best = (indices.clone(), cmax);
+        }
+
+        let Some(path) = critical_path(&order) else {
+            break;
+        };
+        let Some(c) = path.c else {
+            break;
+        };
+        modified[indices[c]].r = order[path.b].r;
+    }
+
+    best
+}
+
+pub fn potts(tasks: Vec<Task>) -> (Vec<Task>, u32) {
+    let (indices, cmax) = potts_indices(&tasks);
+    (indices.iter().map(|&i| tasks[i]).collect(), cmax)
+}
+
+pub fn potts_cmax(tasks: Vec<Task>) -> u32 {
+    potts_indices(&tasks).1
+}
+
+// potts on the instance and on the reversed instance (r and q swapped),
+// when there are two tasks longer than 1/3 of the total processing time
+// both of their relative orders are tried as well, the precedence is forced by adjusting r and q
+pub fn hall_shmoys(tasks: Vec<Task>) -> (Vec<Task>, u32) {
+    let total: u64 = tasks.iter().map(|t| t.p as u64).sum();
+    let large: Vec<usize> = (0..tasks.len())
+        .filter(|&i| 3 * tasks[i].p as u64 > total)
+        .collect();
+
+    let mut instances = vec![tasks.clone()];
+    if let [u, v] = large[..] {
+        for (first, second) in [(u, v), (v, u)] {
+            let mut instance = tasks.clone();
+            instance[second].r = max(instance[second].r, instance[first].r + instance[first].p);
+            instance[first].q = max(instance[first].q, instance[second].q + instance[second].p);
+            instances.push(instance);
+        }
+    }
+
+    let mut best = (Vec::new(), u32::MAX);
+    for instance in instances {
+        let (forward, _) = potts_indices(&instance);
+        let (mut backward, _) = potts_indices(&reverse(&instance));
+        backward.reverse();
+
+        for indices in [forward, backward] {
+            let order: Vec<Task> = indices.iter().map(|&i| tasks[i]).collect();
+            let cmax = cmax(&order);
+            if cmax < best.1 {
+                best = (order, cmax);
+            }
+        }
+    }
+
+    best
+}
+
+pub fn hall_shmoys_cmax(tasks: Vec<Task>) -> u32 {
+    hall_shmoys(tasks).1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::std_heap_impl::schrage_heaps_std_cmax;
+
+    macro_rules! tasks {
+        () => {
+            vec![
+                Task::new(30, 3, 8),
+                Task::new(20, 4, 21),
+                Task::new(10, 5, 7),
+                Task::new(11, 7, 24),
+                Task::new(30, 2, 0),
+                Task::new(13, 6, 26),
+                Task::new(0, 6, 17),
+            ]
+        };
+    }
+
+    fn is_permutation(mut order: Vec<Task>, mut tasks: Vec<Task>) -> bool {
+        order.sort();
+        tasks.sort();
+        order == tasks
+    }
+
+    #[test]
+    fn test_potts() {
+        let (order, cmax) = potts(tasks!());
+        assert!(cmax <= schrage_heaps_std_cmax(tasks!()));
+        assert_eq!(cmax, crate::schedule::cmax(&order));
+        assert!(is_permutation(order, tasks!()));
+    }
+
+    #[test]
+    fn test_hall_shmoys() {
+        let (order, cmax) = hall_shmoys(tasks!());
+        assert!(cmax <= potts_cmax(tasks!()));
+        assert_eq!(cmax, crate::schedule::cmax(&order));
+        assert!(is_permutation(order, tasks!()));
+    }
+
+    #[test]
+    fn test_hall_shmoys_large_tasks() {
+        let tasks = vec![
+            Task::new(0, 40, 0),
+            Task::new(1, 40, 50),
+            Task::new(2, 5, 5),
+            Task::new(3, 5, 1),
+        ];
+        let (order, cmax) = hall_shmoys(tasks.clone());
+        assert_eq!(cmax, crate::schedule::cmax(&order));
+        assert!(cmax <= potts_cmax(tasks.clone()));
+        assert!(is_permutation(order, tasks));
+    }
+
+    #[test]
+    fn test_empty() {
+        assert_eq!(potts(vec![]), (vec![], 0));
+        assert_eq!(hall_shmoys(vec![]), (vec![], 0));
+    }
+}
@@ -0,0 +1,161 @@
+use crate::schedule::order_indices;
+use crate::std_heap_impl::{schrage_heaps_std, schrage_preemptive_heaps_std_cmax};
+use crate::task::Task;
+use alloc::{
+    borrow::ToOwned,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use std::{fs, io, path::Path};
+
+// csv with an r,p,q header
+pub fn to_csv(tasks: &[Task]) -> String {
+    let mut csv = String::from("r,p,q\n");
+    for task in tasks {
+        csv.push_str(&format!("{},{},{}\n", task.r, task.p, task.q));
+    }
+    csv
+}
+
+// the header is required
+pub fn parse_csv(csv: &str) -> Option<Vec<Task>> {
+    let mut lines = csv.lines().map(str::trim).filter(|l| !l.is_empty());
+    if lines.next()?.replace(' ', "") != "r,p,q" {
+        return None;
+    }
+
+    lines
+        .map(|line| {
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let [r, p, q] = fields[..] else {
+                return None;
+            };
+            Some(Task::new(r.parse().ok()?, p.parse().ok()?, q.parse().ok()?))
+        })
+        .collect()
+}
+
+#[cfg(feature = "std")]
+pub fn read_csv(path: impl AsRef<Path>) -> io::Result<Vec<Task>> {
+    parse_csv(&fs::read_to_string(path)?)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid r,p,q csv"))
+}
+
+#[cfg(feature = "std")]
+pub fn write_csv(path: impl AsRef<Path>, tasks: &[Task]) -> io::Result<()> {
+    fs::write(path, to_csv(tasks))
+}
+
+// instances of a schr.data file with their names, the reference results are skipped
+pub fn parse_schr_data(data: &str) -> Option<Vec<(String, Vec<Task>)>> {
+    let mut instances = Vec::new();
+    let mut lines = data.lines().map(str::trim);
+    while let Some(line) = lines.next() {
+        if !(line.starts_with("data") && line.ends_with(':')) {
+            continue;
+        }
+        let name = line.trim_end_matches(':').to_owned();
+        let n: usize = lines.next()?.parse().ok()?;
+        let tasks = lines
+            .by_ref()
+            .take(n)
+            .map(|l| {
+                let mut values = l.split_whitespace().map(|v| v.parse::<u32>().ok());
+                Some(Task::new(values.next()??, values.next()??, values.next()??))
+            })
+            .collect::<Option<Vec<Task>>>()?;
+        if tasks.len() != n {
+            return None;
+        }
+        instances.push((name, tasks));
+    }
+    Some(instances)
+}
+
+// one instance in the schr.data format together with the preemptive and
+// schrage results, so that generated files can be used as test data
+pub fn to_schr_data(name: &str, tasks: &[Task]) -> String {
+    let mut data = format!("{name}:\n{}\n", tasks.len());
+    for task in tasks {
+        data.push_str(&format!("{} {} {}\n", task.r, task.p, task.q));
+    }
+    let (order, cmax) = schrage_heaps_std(tasks.to_vec());
+    let order: Vec<String> = order_indices(&order, tasks)
+        .iter()
+        .map(|idx| (idx + 1).to_string())
+        .collect();
+    data.push_str(&format!(
+        "\nschrpmtn:\n{}\n\nschr:\n{cmax}\n{}\n\n",
+        schrage_preemptive_heaps_std_cmax(tasks.to_vec()),
+        order.join(" ")
+    ));
+    data
+}
+
+#[cfg(feature = "std")]
+pub fn write_schr_data(
+    path: impl AsRef<Path>,
+    instances: &[(String, Vec<Task>)],
+) -> io::Result<()> {
+    let data: String = instances
+        .iter()
+        .map(|(name, tasks)| to_schr_data(name, tasks))
+        .collect();
+    fs::write(path, data)
+}
+
+#[cfg(feature = "std")]
+pub fn read_schr_data(path: impl AsRef<Path>) -> io::Result<Vec<(String, Vec<Task>)>> {
+    parse_schr_data(&fs::read_to_string(path)?)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid schr.data file"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv() {
+        let tasks = vec![Task::new(0, 6, 17), Task::new(10, 5, 7)];
+        let csv = to_csv(&tasks);
+        assert_eq!(csv, "r,p,q\n0,6,17\n10,5,7\n");
+        assert_eq!(parse_csv(&csv), Some(tasks));
+        assert_eq!(
+            parse_csv("r, p, q\n 1, 2, 3 \n\n"),
+            Some(vec![Task::new(1, 2, 3)])
+        );
+        assert_eq!(parse_csv(""), None);
+        assert_eq!(parse_csv("1,2,3\n"), None);
+        assert_eq!(parse_csv("r,p,q\n1,2\n"), None);
+        assert_eq!(parse_csv("r,p,q\n1,2,-3\n"), None);
+    }
+
+    #[test]
+    fn test_schr_data() {
+        let data = "data.000:\n2\n0 27 78\n140 7 67\n\nschr:\n283\n1 2\n\ndata.001:\n1\n1 2 3\n";
+        assert_eq!(
+            parse_schr_data(data),
+            Some(vec![
+                (
+                    "data.000".to_owned(),
+                    vec![Task::new(0, 27, 78), Task::new(140, 7, 67)]
+                ),
+                ("data.001".to_owned(), vec![Task::new(1, 2, 3)]),
+            ])
+        );
+        assert_eq!(parse_schr_data("data.000:\n2\n0 27 78\n"), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_csv_file() {
+        let tasks = vec![Task::new(0, 6, 17), Task::new(10, 5, 7)];
+        let path = std::env::temp_dir().join(format!("schrage_io_{}.csv", std::process::id()));
+        write_csv(&path, &tasks).unwrap();
+        assert_eq!(read_csv(&path).unwrap(), tasks);
+        fs::remove_file(&path).unwrap();
+        assert!(read_csv(&path).is_err());
+    }
+}
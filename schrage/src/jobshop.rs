@@ -0,0 +1,447 @@
+use crate::schedule::order_indices;
+use crate::std_heap_impl::schrage_heaps_std;
+use crate::task::Task;
+use alloc::collections::VecDeque;
+use alloc::{vec, vec::Vec};
+use core::cmp::max;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Operation {
+    pub machine: usize,
+    pub p: u32,
+}
+
+// every job is a sequence of operations, each on a different machine
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct JobShop {
+    pub machines: usize,
+    pub jobs: Vec<Vec<Operation>>,
+}
+
+impl JobShop {
+    // standard format: "jobs machines" in the first line,
+    // then a line per job with "machine time" pairs, machines are numbered from 0
+    pub fn parse(input: &str) -> Option<JobShop> {
+        let mut lines = input.lines().filter(|l| !l.trim().is_empty());
+        let mut header = lines.next()?.split_whitespace();
+        let job_count: usize = header.next()?.parse().ok()?;
+        let machines: usize = header.next()?.parse().ok()?;
+
+        let mut jobs = Vec::with_capacity(job_count);
+        for line in lines.take(job_count) {
+            let values: Vec<u32> = line
+                .split_whitespace()
+                .map(|w| w.parse().ok())
+                .collect::<Option<_>>()?;
+            if !values.len().is_multiple_of(2) {
+                return None;
+            }
+            let job: Vec<Operation> = values
+                .chunks(2)
+                .map(|pair| Operation {
+                    machine: pair[0] as usize,
+                    p: pair[1],
+                })
+                .collect();
+            if job.iter().any(|op| op.machine >= machines) {
+                return None;
+            }
+            jobs.push(job);
+        }
+
+        (jobs.len() == job_count).then_some(JobShop { machines, jobs })
+    }
+}
+
+// operations are numbered job after job, conjunctive arcs go along the jobs,
+// disjunctive arcs are selected by fixing the sequence of operations on a machine
+#[derive(Debug, Clone)]
+pub struct DisjunctiveGraph {
+    p: Vec<u32>,
+    machine: Vec<usize>,
+    next_in_job: Vec<Option<usize>>,
+    first_of_job: Vec<usize>,
+    sequences: Vec<Option<Vec<usize>>>,
+}
+
+impl DisjunctiveGraph {
+    pub fn new(instance: &JobShop) -> DisjunctiveGraph {
+        let mut graph = DisjunctiveGraph {
+            p: Vec::new(),
+            machine: Vec::new(),
+            next_in_job: Vec::new(),
+            first_of_job: Vec::new(),
+            sequences: vec![None; instance.machines],
+        };
+        for job in &instance.jobs {
+            graph.first_of_job.push(graph.p.len());
+            for (k, op) in job.iter().enumerate() {
+                let id = graph.p.len();
+                graph.p.push(op.p);
+                graph.machine.push(op.machine);
+                graph
+                    .next_in_job
+                    .push((k + 1 < job.len()).then_some(id + 1));
+            }
+        }
+        graph
+    }
+
+    pub fn operation_count(&self) -> usize {
+        self.p.len()
+    }
+
+    // (job, position in the job) of the operation
+    pub fn operation(&self, id: usize) -> (usize, usize) {
+        let job = self.first_of_job.partition_point(|&first| first <= id) - 1;
+        (job, id - self.first_of_job[job])
+    }
+
+    pub fn operations_on(&self, machine: usize) -> Vec<usize> {
+        (0..self.operation_count())
+            .filter(|&id| self.machine[id] == machine)
+            .collect()
+    }
+
+    pub fn fix_machine(&mut self, machine: usize, sequence: Vec<usize>) {
+        self.sequences[machine] = Some(sequence);
+    }
+
+    pub fn release_machine(&mut self, machine: usize) {
+        self.sequences[machine] = None;
+    }
+
+    pub fn sequence(&self, machine: usize) -> Option<&[usize]> {
+        self.sequences[machine].as_deref()
+    }
+
+    fn successors(&self) -> Vec<Vec<usize>> {
+        let mut successors: Vec<Vec<usize>> = self
+            .next_in_job
+            .iter()
+            .map(|next| next.iter().copied().collect())
+            .collect();
+        for sequence in self.sequences.iter().flatten() {
+            for pair in sequence.windows(2) {
+                successors[pair[0]].push(pair[1]);
+            }
+        }
+        successors
+    }
+
+    // operations in a topological order of the graph, None when the selection has a cycle
+    pub fn topological_order(&self) -> Option<Vec<usize>> {
+        self.topological(&self.successors())
+    }
+
+    fn topological(&self, successors: &[Vec<usize>]) -> Option<Vec<usize>> {
+        let n = self.operation_count();
+        let mut in_degree = vec![0; n];
+        for &s in successors.iter().flatten() {
+            in_degree[s] += 1;
+        }
+
+        let mut queue: VecDeque<usize> = (0..n).filter(|&id| in_degree[id] == 0).collect();
+        let mut topological = Vec::with_capacity(n);
+        while let Some(id) = queue.pop_front() {
+            topological.push(id);
+            for &s in &successors[id] {
+                in_degree[s] -= 1;
+                if in_degree[s] == 0 {
+                    queue.push_back(s);
+                }
+            }
+        }
+        (topological.len() == n).then_some(topological)
+    }
+
+    // heads (r) and tails (q) of every operation as longest paths in the graph,
+    // None when the selection has a cycle
+    pub fn heads_tails(&self) -> Option<(Vec<u32>, Vec<u32>)> {
+        let n = self.operation_count();
+        let successors = self.successors();
+        let topological = self.topological(&successors)?;
+
+        let mut heads = vec![0; n];
+        for &id in &topological {
+            for &s in &successors[id] {
+                heads[s] = max(heads[s], heads[id] + self.p[id]);
+            }
+        }
+        let mut tails = vec![0; n];
+        for &id in topological.iter().rev() {
+            for &s in &successors[id] {
+                tails[id] = max(tails[id], self.p[s] + tails[s]);
+            }
+        }
+        Some((heads, tails))
+    }
+
+    pub fn makespan(&self) -> Option<u32> {
+        let (heads, tails) = self.heads_tails()?;
+        Some(
+            (0..self.operation_count())
+                .map(|id| heads[id] + self.p[id] + tails[id])
+                .max()
+                .unwrap_or(0),
+        )
+    }
+
+    // one machine problem of the machine with heads and tails of the current selection,
+    // returns the operation sequence and its cmax
+    fn solve_machine(
+        &self,
+        machine: usize,
+        heads: &[u32],
+        tails: &[u32],
+        alg: fn(Vec<Task>) -> (Vec<Task>, u32),
+    ) -> (Vec<usize>, u32) {
+        let operations = self.operations_on(machine);
+        let tasks: Vec<Task> = operations
+            .iter()
+            .map(|&id| Task::new(heads[id], self.p[id], tails[id]))
+            .collect();
+
+        let (order, cmax) = alg(tasks.clone());
+        let sequence = order_indices(&order, &tasks)
+            .into_iter()
+            .map(|idx| operations[idx])
+            .collect();
+        (sequence, cmax)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct JobShopSchedule {
+    // operations of every machine as (job, position in the job)
+    pub machine_orders: Vec<Vec<(usize, usize)>>,
+    // start time of every operation, indexed like the jobs of the instance
+    pub start_times: Vec<Vec<u32>>,
+    pub makespan: u32,
+}
+
+// shifting bottleneck procedure, alg solves the one machine subproblems
+pub fn shifting_bottleneck(
+    instance: &JobShop,
+    alg: fn(Vec<Task>) -> (Vec<Task>, u32),
+) -> JobShopSchedule {
+    let mut graph = DisjunctiveGraph::new(instance);
+    let mut unscheduled: Vec<usize> = (0..instance.machines).collect();
+    let mut scheduled: Vec<usize> = Vec::new();
+
+    while !unscheduled.is_empty() {
+        // unwraps are safe, fix_acyclic never leaves a cycle in the graph
+        let (heads, tails) = graph.heads_tails().unwrap();
+
+        // the bottleneck is the machine with the largest one machine cmax
+        let (position, sequence) = unscheduled
+            .iter()
+            .enumerate()
+            .map(|(position, &m)| (position, graph.solve_machine(m, &heads, &tails, alg)))
+            .max_by_key(|(_, (_, cmax))| *cmax)
+            .map(|(position, (sequence, _))| (position, sequence))
+            .unwrap();
+        let bottleneck = unscheduled.swap_remove(position);
+        fix_acyclic(&mut graph, bottleneck, sequence, &heads, &tails);
+        scheduled.push(bottleneck);
+
+        // reoptimization of the machines scheduled before, repeated while it helps
+        loop {
+            let start = graph.makespan().unwrap();
+            for &m in &scheduled {
+                let previous = graph.sequence(m).unwrap().to_vec();
+                let makespan = graph.makespan().unwrap();
+                graph.release_machine(m);
+                let (heads, tails) = graph.heads_tails().unwrap();
+                let (sequence, _) = graph.solve_machine(m, &heads, &tails, alg);
+                fix_acyclic(&mut graph, m, sequence, &heads, &tails);
+                if graph.makespan().unwrap() > makespan {
+                    graph.fix_machine(m, previous);
+                }
+            }
+            if graph.makespan().unwrap() >= start {
+                break;
+            }
+        }
+    }
+
+    let (heads, _) = graph.heads_tails().unwrap();
+    let mut start_times: Vec<Vec<u32>> =
+        instance.jobs.iter().map(|job| vec![0; job.len()]).collect();
+    for (id, &head) in heads.iter().enumerate() {
+        let (job, k) = graph.operation(id);
+        start_times[job][k] = head;
+    }
+
+    JobShopSchedule {
+        machine_orders: (0..instance.machines)
+            .map(|m| {
+                graph
+                    .sequence(m)
+                    .unwrap_or(&[])
+                    .iter()
+                    .map(|&id| graph.operation(id))
+                    .collect()
+            })
+            .collect(),
+        start_times,
+        makespan: graph.makespan().unwrap(),
+    }
+}
+
+pub fn shifting_bottleneck_carlier(instance: &JobShop) -> JobShopSchedule {
+    shifting_bottleneck(instance, crate::carlier::carlier)
+}
+
+// an optimal one machine sequence can break ties against paths already in the graph,
+// schrage follows them unless operations of zero length leave heads and tails equal,
+// the last resort is the topological order of the graph without the machine,
+// it follows every path so it cannot close a cycle
+// the graph must be acyclic while the machine is not fixed
+fn fix_acyclic(
+    graph: &mut DisjunctiveGraph,
+    machine: usize,
+    sequence: Vec<usize>,
+    heads: &[u32],
+    tails: &[u32],
+) {
+    graph.fix_machine(machine, sequence);
+    if graph.topological_order().is_some() {
+        return;
+    }
+    let (sequence, _) = graph.solve_machine(machine, heads, tails, schrage_heaps_std);
+    graph.fix_machine(machine, sequence);
+    if graph.topological_order().is_some() {
+        return;
+    }
+    graph.release_machine(machine);
+    let mut position = vec![0; graph.operation_count()];
+    for (k, id) in graph.topological_order().unwrap().into_iter().enumerate() {
+        position[id] = k;
+    }
+    let mut sequence = graph.operations_on(machine);
+    sequence.sort_by_key(|&id| position[id]);
+    graph.fix_machine(machine, sequence);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn load(name: &str) -> JobShop {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("test_data");
+        path.push(name);
+        JobShop::parse(&std::fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    // every job keeps its operation order, machines do one operation at a time
+    fn assert_feasible(instance: &JobShop, schedule: &JobShopSchedule) {
+        let mut makespan = 0;
+        for (job, ops) in instance.jobs.iter().enumerate() {
+            for k in 0..ops.len() {
+                let end = schedule.start_times[job][k] + ops[k].p;
+                if k + 1 < ops.len() {
+                    assert!(end <= schedule.start_times[job][k + 1]);
+                }
+                makespan = max(makespan, end);
+            }
+        }
+        assert_eq!(makespan, schedule.makespan);
+
+        for (m, order) in schedule.machine_orders.iter().enumerate() {
+            let count = instance
+                .jobs
+                .iter()
+                .flatten()
+                .filter(|op| op.machine == m)
+                .count();
+            assert_eq!(order.len(), count);
+            for pair in order.windows(2) {
+                let (job, k) = pair[0];
+                let (next_job, next_k) = pair[1];
+                assert_eq!(instance.jobs[job][k].machine, m);
+                assert!(
+                    schedule.start_times[job][k] + instance.jobs[job][k].p
+                        <= schedule.start_times[next_job][next_k]
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let instance = load("ft06.data");
+        assert_eq!(instance.machines, 6);
+        assert_eq!(instance.jobs.len(), 6);
+        assert_eq!(instance.jobs[0][0], Operation { machine: 2, p: 1 });
+        assert_eq!(JobShop::parse("1 1\n0 5 1"), None);
+        assert_eq!(JobShop::parse("1 1\n3 5"), None);
+    }
+
+    #[test]
+    fn test_heads_tails() {
+        let instance = JobShop::parse("2 2\n0 3 1 2\n1 4 0 1").unwrap();
+        let mut graph = DisjunctiveGraph::new(&instance);
+        let (heads, tails) = graph.heads_tails().unwrap();
+        assert_eq!(heads, vec![0, 3, 0, 4]);
+        assert_eq!(tails, vec![2, 0, 1, 0]);
+        assert_eq!(graph.makespan(), Some(5));
+
+        // machine 0: second job first
+        graph.fix_machine(0, vec![3, 0]);
+        assert_eq!(graph.makespan(), Some(10));
+        // cycle with the job order
+        graph.fix_machine(1, vec![1, 2]);
+        assert_eq!(graph.heads_tails(), None);
+    }
+
+    #[test]
+    fn test_ft06() {
+        let instance = load("ft06.data");
+        let schedule = shifting_bottleneck_carlier(&instance);
+        assert_feasible(&instance, &schedule);
+        // optimum of ft06 is 55
+        assert_eq!(schedule.makespan, 55);
+    }
+
+    #[test]
+    fn test_la01() {
+        let instance = load("la01.data");
+        let schedule = shifting_bottleneck_carlier(&instance);
+        assert_feasible(&instance, &schedule);
+        // optimum of la01 is 666 and shifting bottleneck finds it
+        assert_eq!(schedule.makespan, 666);
+    }
+
+    // equal heads and tails everywhere, any one machine order is optimal
+    // and schrage can pick one against the jobs
+    #[test]
+    fn test_zero_length_operations() {
+        let instance = JobShop::parse("3 2\n0 0 1 0\n1 0 0 0\n0 0 1 0").unwrap();
+        let schedule = shifting_bottleneck_carlier(&instance);
+        assert_feasible(&instance, &schedule);
+        assert_eq!(schedule.makespan, 0);
+
+        let instance = JobShop::parse("2 2\n0 0 1 3\n1 0 0 2").unwrap();
+        let schedule = shifting_bottleneck(&instance, schrage_heaps_std);
+        assert_feasible(&instance, &schedule);
+        assert_eq!(schedule.makespan, 3);
+    }
+
+    #[test]
+    fn test_fix_acyclic() {
+        let instance = JobShop::parse("2 2\n0 0 1 0\n1 0 0 0").unwrap();
+        let mut graph = DisjunctiveGraph::new(&instance);
+        graph.fix_machine(1, vec![1, 2]);
+        // machine 0 first with the second job closes a cycle
+        let zeros = vec![0; graph.operation_count()];
+        fix_acyclic(&mut graph, 0, vec![3, 0], &zeros, &zeros);
+        assert_eq!(graph.sequence(0), Some(&[0, 3][..]));
+        assert_eq!(graph.makespan(), Some(0));
+    }
+}
@@ -1,7 +1,37 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "parallel")]
+pub mod batch;
+pub mod calendar;
+pub mod carlier;
+pub mod cmax_tree;
 pub mod custom_heap_impl;
+pub mod dispatch;
+pub mod due_dates;
+pub mod flowshop;
+pub mod gantt;
+pub mod generator;
+pub mod heuristics;
+pub mod io;
+pub mod jobshop;
+#[cfg(feature = "std")]
+pub mod local_search;
+#[cfg(feature = "std")]
+pub mod metaheuristics;
+pub mod multi_machine;
+pub mod objectives;
+pub mod precedence;
+pub mod schedule;
+pub mod setup_times;
+pub mod soa_impl;
 pub mod std_heap_impl;
 pub mod std_vecs_impl;
+pub mod symmetry;
 pub mod task;
+pub mod trace;
+pub mod validation;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests;
@@ -0,0 +1,165 @@
+use crate::cmax_tree::CmaxTree;
+use crate::schedule::Schedule;
+use crate::std_heap_impl::schrage_heaps_std;
+use crate::task::Task;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighbourhood {
+    // swap of two neighbouring tasks
+    AdjacentSwap,
+    // swap of any two tasks
+    Swap,
+    // a task is taken out and put at any other position
+    Insertion,
+}
+
+#[derive(Debug, Clone)]
+pub struct LocalSearchConfig {
+    // neighbourhoods are searched in this order, the first improving move is applied
+    pub neighbourhoods: Vec<Neighbourhood>,
+    // number of applied moves
+    pub max_iterations: usize,
+    pub time_limit: Option<Duration>,
+}
+
+impl Default for LocalSearchConfig {
+    fn default() -> Self {
+        LocalSearchConfig {
+            neighbourhoods: vec![
+                Neighbourhood::AdjacentSwap,
+                Neighbourhood::Swap,
+                Neighbourhood::Insertion,
+            ],
+            max_iterations: usize::MAX,
+            time_limit: None,
+        }
+    }
+}
+
+// first improvement local search, stops in a local optimum of all neighbourhoods
+// or when one of the limits is reached
+pub fn local_search(schedule: Schedule, config: &LocalSearchConfig) -> Schedule {
+    let start = Instant::now();
+    let out_of_time = || {
+        config
+            .time_limit
+            .is_some_and(|limit| start.elapsed() >= limit)
+    };
+    let mut tree = CmaxTree::new(schedule.order);
+    let n = tree.len();
+
+    'search: for _ in 0..config.max_iterations {
+        let cmax = tree.cmax();
+        for neighbourhood in &config.neighbourhoods {
+            for i in 0..n {
+                if out_of_time() {
+                    break 'search;
+                }
+                match neighbourhood {
+                    Neighbourhood::AdjacentSwap => {
+                        if i + 1 < n && tree.cmax_with_swap(i, i + 1) < cmax {
+                            tree.swap(i, i + 1);
+                            continue 'search;
+                        }
+                    }
+                    Neighbourhood::Swap => {
+                        if let Some(j) = (i + 1..n).find(|&j| tree.cmax_with_swap(i, j) < cmax) {
+                            tree.swap(i, j);
+                            continue 'search;
+                        }
+                    }
+                    Neighbourhood::Insertion => {
+                        if let Some(j) = (0..n).find(|&j| tree.cmax_with_insertion(i, j) < cmax) {
+                            tree.insert(i, j);
+                            continue 'search;
+                        }
+                    }
+                }
+            }
+        }
+        // no improving move in any neighbourhood
+        break;
+    }
+
+    let cmax = tree.cmax();
+    Schedule {
+        order: tree.into_order(),
+        cmax,
+    }
+}
+
+pub fn schrage_local_search(tasks: Vec<Task>, config: &LocalSearchConfig) -> Schedule {
+    local_search(schrage_heaps_std(tasks).into(), config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schedule::cmax;
+
+    macro_rules! tasks {
+        () => {
+            vec![
+                Task::new(30, 3, 8),
+                Task::new(20, 4, 21),
+                Task::new(10, 5, 7),
+                Task::new(11, 7, 24),
+                Task::new(30, 2, 0),
+                Task::new(13, 6, 26),
+                Task::new(0, 6, 17),
+            ]
+        };
+    }
+
+    #[test]
+    fn test_local_search() {
+        let schedule = schrage_local_search(tasks!(), &LocalSearchConfig::default());
+        assert!(schedule.cmax <= 53);
+        assert_eq!(schedule.cmax, cmax(&schedule.order));
+
+        // local optimum, no single move improves the order
+        let tree = CmaxTree::new(schedule.order.clone());
+        for i in 0..tree.len() {
+            for j in 0..tree.len() {
+                assert!(tree.cmax_with_swap(i, j) >= schedule.cmax);
+                assert!(tree.cmax_with_insertion(i, j) >= schedule.cmax);
+            }
+        }
+    }
+
+    #[test]
+    fn test_local_search_limits() {
+        let config = LocalSearchConfig {
+            max_iterations: 0,
+            ..Default::default()
+        };
+        let schedule = schrage_local_search(tasks!(), &config);
+        assert_eq!(schedule, schrage_heaps_std(tasks!()).into());
+
+        let config = LocalSearchConfig {
+            time_limit: Some(Duration::ZERO),
+            ..Default::default()
+        };
+        assert_eq!(schrage_local_search(tasks!(), &config).cmax, 53);
+    }
+
+    #[test]
+    fn test_local_search_adjacent_only() {
+        let config = LocalSearchConfig {
+            neighbourhoods: vec![Neighbourhood::AdjacentSwap],
+            ..Default::default()
+        };
+        let schedule = schrage_local_search(tasks!(), &config);
+        let tree = CmaxTree::new(schedule.order.clone());
+        for i in 1..tree.len() {
+            assert!(tree.cmax_with_swap(i - 1, i) >= schedule.cmax);
+        }
+    }
+
+    #[test]
+    fn test_local_search_empty() {
+        let schedule = local_search(Schedule::default(), &LocalSearchConfig::default());
+        assert_eq!(schedule, Schedule::default());
+    }
+}
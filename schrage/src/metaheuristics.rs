@@ -0,0 +1,321 @@
+use crate::cmax_tree::CmaxTree;
+use crate::schedule::Schedule;
+use crate::std_heap_impl::{schrage_heaps_std, schrage_preemptive_heaps_std_cmax};
+use crate::task::Task;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::time::{Duration, Instant};
+
+// passed to the progress callback after every move, current is the schedule
+// the search stands on and best the best one found so far
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Progress {
+    pub iteration: usize,
+    pub elapsed: Duration,
+    pub current_cmax: u32,
+    pub best_cmax: u32,
+    // preemptive schrage, no schedule can be better than that
+    pub lower_bound: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct TabuSearchConfig {
+    pub seed: u64,
+    pub time_limit: Duration,
+    pub max_iterations: usize,
+    // number of iterations a moved task stays tabu
+    pub tenure: usize,
+    // random insertion moves evaluated in every iteration,
+    // the whole neighbourhood is too big for large instances
+    pub sample_size: usize,
+}
+
+impl Default for TabuSearchConfig {
+    fn default() -> Self {
+        TabuSearchConfig {
+            seed: 0,
+            time_limit: Duration::from_secs(1),
+            max_iterations: usize::MAX,
+            tenure: 10,
+            sample_size: 100,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SimulatedAnnealingConfig {
+    pub seed: u64,
+    pub time_limit: Duration,
+    pub max_iterations: usize,
+    // None starts with the mean processing time
+    pub initial_temperature: Option<f64>,
+    // temperature is multiplied by it after every iteration
+    pub cooling: f64,
+}
+
+impl Default for SimulatedAnnealingConfig {
+    fn default() -> Self {
+        SimulatedAnnealingConfig {
+            seed: 0,
+            time_limit: Duration::from_secs(1),
+            max_iterations: usize::MAX,
+            initial_temperature: None,
+            cooling: 0.9999,
+        }
+    }
+}
+
+// random insertion move, the task is always moved somewhere else
+fn random_move(rng: &mut StdRng, n: usize) -> (usize, usize) {
+    let from = rng.gen_range(0..n);
+    let mut to = rng.gen_range(0..n - 1);
+    if to >= from {
+        to += 1;
+    }
+    (from, to)
+}
+
+pub fn tabu_search(
+    tasks: Vec<Task>,
+    config: &TabuSearchConfig,
+    mut progress: impl FnMut(&Progress),
+) -> Schedule {
+    let start = Instant::now();
+    let lower_bound = schrage_preemptive_heaps_std_cmax(tasks.clone());
+    let mut best: Schedule = schrage_heaps_std(tasks).into();
+    let n = best.order.len();
+    if n < 2 {
+        return best;
+    }
+
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let mut tree = CmaxTree::new(best.order.clone());
+    // ids follow the tasks through the moves so the tabu list does not depend on positions
+    let mut ids: Vec<usize> = (0..n).collect();
+    let mut tabu_until = vec![0; n];
+
+    for iteration in 1..=config.max_iterations {
+        if start.elapsed() >= config.time_limit {
+            break;
+        }
+
+        let mut chosen: Option<(u32, usize, usize)> = None;
+        for _ in 0..config.sample_size {
+            let (from, to) = random_move(&mut rng, n);
+            let cmax = tree.cmax_with_insertion(from, to);
+            // aspiration, a tabu move is allowed when it gives a new best schedule
+            if tabu_until[ids[from]] > iteration && cmax >= best.cmax {
+                continue;
+            }
+            if chosen.is_none_or(|(chosen_cmax, _, _)| cmax < chosen_cmax) {
+                chosen = Some((cmax, from, to));
+            }
+        }
+
+        let Some((cmax, from, to)) = chosen else {
+            continue;
+        };
+        tabu_until[ids[from]] = iteration + config.tenure;
+        tree.insert(from, to);
+        if from < to {
+            ids[from..=to].rotate_left(1);
+        } else {
+            ids[to..=from].rotate_right(1);
+        }
+
+        if cmax < best.cmax {
+            best = Schedule {
+                order: tree.order().to_vec(),
+                cmax,
+            };
+        }
+        progress(&Progress {
+            iteration,
+            elapsed: start.elapsed(),
+            current_cmax: tree.cmax(),
+            best_cmax: best.cmax,
+            lower_bound,
+        });
+        if best.cmax == lower_bound {
+            break;
+        }
+    }
+
+    best
+}
+
+pub fn simulated_annealing(
+    tasks: Vec<Task>,
+    config: &SimulatedAnnealingConfig,
+    mut progress: impl FnMut(&Progress),
+) -> Schedule {
+    let start = Instant::now();
+    let lower_bound = schrage_preemptive_heaps_std_cmax(tasks.clone());
+    let mut best: Schedule = schrage_heaps_std(tasks).into();
+    let n = best.order.len();
+    if n < 2 {
+        return best;
+    }
+
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let mut tree = CmaxTree::new(best.order.clone());
+    let mut temperature = config
+        .initial_temperature
+        .unwrap_or_else(|| best.order.iter().map(|t| t.p as f64).sum::<f64>() / n as f64);
+
+    for iteration in 1..=config.max_iterations {
+        if start.elapsed() >= config.time_limit {
+            break;
+        }
+
+        let current = tree.cmax();
+        let swap = rng.gen_bool(0.5);
+        let (i, j) = random_move(&mut rng, n);
+        let cmax = if swap {
+            tree.cmax_with_swap(i, j)
+        } else {
+            tree.cmax_with_insertion(i, j)
+        };
+
+        let accepted =
+            cmax <= current || rng.gen::<f64>() < (-((cmax - current) as f64) / temperature).exp();
+        if accepted {
+            if swap {
+                tree.swap(i, j);
+            } else {
+                tree.insert(i, j);
+            }
+        }
+        temperature *= config.cooling;
+
+        // best is never worse than current so such move is always accepted
+        if cmax < best.cmax {
+            best = Schedule {
+                order: tree.order().to_vec(),
+                cmax,
+            };
+        }
+        progress(&Progress {
+            iteration,
+            elapsed: start.elapsed(),
+            current_cmax: tree.cmax(),
+            best_cmax: best.cmax,
+            lower_bound,
+        });
+        if best.cmax == lower_bound {
+            break;
+        }
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schedule::cmax;
+
+    macro_rules! tasks {
+        () => {
+            vec![
+                Task::new(30, 3, 8),
+                Task::new(20, 4, 21),
+                Task::new(10, 5, 7),
+                Task::new(11, 7, 24),
+                Task::new(30, 2, 0),
+                Task::new(13, 6, 26),
+                Task::new(0, 6, 17),
+            ]
+        };
+    }
+
+    fn is_permutation(mut order: Vec<Task>, mut tasks: Vec<Task>) -> bool {
+        order.sort();
+        tasks.sort();
+        order == tasks
+    }
+
+    #[test]
+    fn test_tabu_search() {
+        let config = TabuSearchConfig {
+            max_iterations: 1000,
+            ..Default::default()
+        };
+        let mut reports = Vec::new();
+        let schedule = tabu_search(tasks!(), &config, |p| reports.push(*p));
+        assert_eq!(schedule.cmax, cmax(&schedule.order));
+        assert_eq!(schedule.cmax, 50);
+        assert!(is_permutation(schedule.order, tasks!()));
+        assert!(reports.windows(2).all(|w| w[1].best_cmax <= w[0].best_cmax));
+        assert!(reports.iter().all(|p| p.current_cmax >= p.best_cmax));
+        assert_eq!(reports.last().unwrap().best_cmax, 50);
+    }
+
+    #[test]
+    fn test_simulated_annealing() {
+        let config = SimulatedAnnealingConfig {
+            max_iterations: 10000,
+            ..Default::default()
+        };
+        let schedule = simulated_annealing(tasks!(), &config, |_| {});
+        assert_eq!(schedule.cmax, cmax(&schedule.order));
+        assert!(schedule.cmax <= 53);
+        assert!(is_permutation(schedule.order, tasks!()));
+    }
+
+    #[test]
+    fn test_progress_current() {
+        // hot enough that worse moves are accepted, the current schedule
+        // then differs from the best one
+        let config = SimulatedAnnealingConfig {
+            max_iterations: 200,
+            initial_temperature: Some(1000.0),
+            cooling: 1.0,
+            ..Default::default()
+        };
+        let mut reports = Vec::new();
+        simulated_annealing(tasks!(), &config, |p| reports.push(*p));
+        assert_eq!(reports.len(), 200);
+        assert!(reports.iter().all(|p| p.current_cmax >= p.best_cmax));
+        assert!(reports.iter().any(|p| p.current_cmax > p.best_cmax));
+        assert!(reports.iter().any(|p| p.current_cmax > 53));
+    }
+
+    #[test]
+    fn test_same_seed_same_schedule() {
+        let config = SimulatedAnnealingConfig {
+            seed: 7,
+            max_iterations: 500,
+            time_limit: Duration::from_secs(60),
+            ..Default::default()
+        };
+        assert_eq!(
+            simulated_annealing(tasks!(), &config, |_| {}),
+            simulated_annealing(tasks!(), &config, |_| {})
+        );
+        let config = TabuSearchConfig {
+            seed: 7,
+            max_iterations: 500,
+            time_limit: Duration::from_secs(60),
+            ..Default::default()
+        };
+        assert_eq!(
+            tabu_search(tasks!(), &config, |_| {}),
+            tabu_search(tasks!(), &config, |_| {})
+        );
+    }
+
+    #[test]
+    fn test_no_time() {
+        let config = TabuSearchConfig {
+            time_limit: Duration::ZERO,
+            ..Default::default()
+        };
+        assert_eq!(tabu_search(tasks!(), &config, |_| {}).cmax, 53);
+        assert_eq!(
+            tabu_search(vec![Task::new(1, 2, 3)], &config, |_| {}).cmax,
+            6
+        );
+    }
+}
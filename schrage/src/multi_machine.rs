@@ -0,0 +1,184 @@
+use crate::std_heap_impl::schrage_preemptive_heaps_std_cmax;
+use crate::task::{QInvariant, RInvariant, Task};
+use alloc::collections::BinaryHeap;
+use alloc::{vec, vec::Vec};
+use core::cmp::{max, Reverse};
+
+// schrage on identical parallel machines, the ready task with the largest q
+// goes to the machine that becomes free first, None without machines
+pub fn schrage_multi_machine(tasks: Vec<Task>, machines: usize) -> Option<(Vec<Vec<Task>>, u32)> {
+    if machines == 0 {
+        return None;
+    }
+
+    let mut available_tasks: BinaryHeap<QInvariant> = BinaryHeap::new();
+    let mut unavailable_tasks: BinaryHeap<RInvariant> = tasks.iter().map(|t| t.into()).collect();
+    // (time the machine is free, machine), the earliest one on top
+    let mut free_machines: BinaryHeap<Reverse<(u32, usize)>> =
+        (0..machines).map(|m| Reverse((0, m))).collect();
+    let mut cmax = 0;
+    let mut orders = vec![Vec::new(); machines];
+
+    while !available_tasks.is_empty() || !unavailable_tasks.is_empty() {
+        // unwrap is safe, there is at least one machine
+        let Reverse((mut t, machine)) = free_machines.pop().unwrap();
+
+        while !unavailable_tasks.is_empty() && unavailable_tasks.peek().unwrap().0.r <= t {
+            let task = unavailable_tasks.pop().unwrap().0;
+            available_tasks.push(task.into());
+        }
+        if available_tasks.is_empty() {
+            // the machine waits for the next ready time
+            t = unavailable_tasks.peek().unwrap().0.r;
+            free_machines.push(Reverse((t, machine)));
+            continue;
+        }
+
+        let task = available_tasks.pop().unwrap().0;
+        t += task.p;
+        cmax = max(cmax, t + task.q);
+        orders[machine].push(task);
+        free_machines.push(Reverse((t, machine)));
+    }
+
+    Some((orders, cmax))
+}
+
+pub fn schrage_multi_machine_cmax(tasks: Vec<Task>, machines: usize) -> Option<u32> {
+    Some(schrage_multi_machine(tasks, machines)?.1)
+}
+
+// relaxation where a task can be split between all machines at once,
+// m machines then behave like one machine m times faster solved by preemptive schrage
+// (computed with time scaled by m), no schedule ends before its longest task either,
+// when the scaled times do not fit in u32 only the longest task is left as the bound,
+// None without machines
+pub fn multi_machine_lower_bound(tasks: Vec<Task>, machines: usize) -> Option<u32> {
+    if machines == 0 {
+        return None;
+    }
+    let longest = tasks
+        .iter()
+        .map(|t| t.r.saturating_add(t.p).saturating_add(t.q))
+        .max()
+        .unwrap_or(0);
+    let Ok(m) = u32::try_from(machines) else {
+        return Some(longest);
+    };
+    let scaled: Option<Vec<Task>> = tasks
+        .iter()
+        .map(|t| Some(Task::new(t.r.checked_mul(m)?, t.p, t.q.checked_mul(m)?)))
+        .collect();
+    match scaled {
+        Some(scaled) if fits(&scaled) => Some(max(
+            longest,
+            schrage_preemptive_heaps_std_cmax(scaled).div_ceil(m),
+        )),
+        _ => Some(longest),
+    }
+}
+
+// every schedule without needless idle time ends by the last ready time
+// plus the whole work plus the longest tail
+fn fits(tasks: &[Task]) -> bool {
+    let last_r = tasks.iter().map(|t| t.r).max().unwrap_or(0);
+    let longest_q = tasks.iter().map(|t| t.q).max().unwrap_or(0);
+    tasks
+        .iter()
+        .try_fold(last_r, |end, t| end.checked_add(t.p))
+        .and_then(|end| end.checked_add(longest_q))
+        .is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::std_heap_impl::{schrage_heaps_std, schrage_preemptive_heaps_std_cmax};
+
+    macro_rules! tasks {
+        () => {
+            vec![
+                Task::new(30, 3, 8),
+                Task::new(20, 4, 21),
+                Task::new(10, 5, 7),
+                Task::new(11, 7, 24),
+                Task::new(30, 2, 0),
+                Task::new(13, 6, 26),
+                Task::new(0, 6, 17),
+            ]
+        };
+    }
+
+    #[test]
+    fn test_one_machine() {
+        let (orders, cmax) = schrage_multi_machine(tasks!(), 1).unwrap();
+        assert_eq!((orders[0].clone(), cmax), schrage_heaps_std(tasks!()));
+        assert_eq!(
+            multi_machine_lower_bound(tasks!(), 1),
+            Some(schrage_preemptive_heaps_std_cmax(tasks!()))
+        );
+    }
+
+    // machine finishing times recomputed from the orders
+    fn cmax_of(orders: &[Vec<Task>]) -> u32 {
+        orders
+            .iter()
+            .map(|order| crate::schedule::cmax(order))
+            .max()
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn test_two_machines() {
+        let (orders, cmax) = schrage_multi_machine(tasks!(), 2).unwrap();
+        assert_eq!(
+            orders[0],
+            vec![
+                Task::new(0, 6, 17),
+                Task::new(10, 5, 7),
+                Task::new(13, 6, 26),
+                Task::new(30, 3, 8),
+            ]
+        );
+        assert_eq!(
+            orders[1],
+            vec![
+                Task::new(11, 7, 24),
+                Task::new(20, 4, 21),
+                Task::new(30, 2, 0)
+            ]
+        );
+        assert_eq!(cmax, 47);
+        assert_eq!(cmax, cmax_of(&orders));
+        assert_eq!(multi_machine_lower_bound(tasks!(), 2), Some(45));
+    }
+
+    #[test]
+    fn test_more_machines_than_tasks() {
+        // every task starts at its ready time
+        let (orders, cmax) = schrage_multi_machine(tasks!(), 10).unwrap();
+        assert_eq!(cmax, 45);
+        assert_eq!(cmax, cmax_of(&orders));
+        assert_eq!(multi_machine_lower_bound(tasks!(), 10), Some(45));
+    }
+
+    #[test]
+    fn test_no_machines() {
+        assert_eq!(schrage_multi_machine(tasks!(), 0), None);
+        assert_eq!(schrage_multi_machine_cmax(tasks!(), 0), None);
+        assert_eq!(multi_machine_lower_bound(tasks!(), 0), None);
+        assert_eq!(schrage_multi_machine(vec![], 3), Some((vec![vec![]; 3], 0)));
+    }
+
+    #[test]
+    fn test_scaled_overflow() {
+        // r * m does not fit in u32, only the longest task is left
+        let tasks = vec![Task::new(u32::MAX / 2, 10, 5), Task::new(0, 3, 1)];
+        assert_eq!(multi_machine_lower_bound(tasks, 3), Some(u32::MAX / 2 + 15));
+        let tasks = vec![Task::new(1 << 20, 1 << 30, 1 << 20); 8];
+        assert_eq!(
+            multi_machine_lower_bound(tasks, 2),
+            Some((1 << 21) + (1 << 30))
+        );
+    }
+}
@@ -0,0 +1,150 @@
+use crate::task::{Job, Task};
+use alloc::vec::Vec;
+use core::cmp::max;
+
+// criteria of an order executed without idle time beyond the ready times,
+// due dates are d = k - q as in due_dates::to_due_dates, k is due_dates::due_date_horizon,
+// orders of plain tasks are evaluated with weight 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Objectives {
+    pub cmax: u32,
+    pub weighted_completion: u64,
+    pub total_tardiness: u64,
+    pub weighted_tardiness: u64,
+    pub late_jobs: usize,
+    // none for an empty order, as in due_dates::lmax
+    pub lmax: Option<i64>,
+}
+
+pub fn completion_times<J: Job>(order: &[J]) -> Vec<u32> {
+    let mut t = 0;
+    order
+        .iter()
+        .map(|job| {
+            let task = job.task();
+            t = max(t, task.r) + task.p;
+            t
+        })
+        .collect()
+}
+
+fn tardiness(task: &Task, completion: u32, k: u32) -> u64 {
+    (completion as i64 + task.q as i64 - k as i64).max(0) as u64
+}
+
+pub fn evaluate<J: Job>(order: &[J], k: u32) -> Objectives {
+    let mut objectives = Objectives {
+        cmax: 0,
+        weighted_completion: 0,
+        total_tardiness: 0,
+        weighted_tardiness: 0,
+        late_jobs: 0,
+        lmax: None,
+    };
+    for (job, c) in order.iter().zip(completion_times(order)) {
+        let tardiness = tardiness(job.task(), c, k);
+        objectives.cmax = max(objectives.cmax, c + job.task().q);
+        objectives.weighted_completion += job.weight() as u64 * c as u64;
+        objectives.total_tardiness += tardiness;
+        objectives.weighted_tardiness += job.weight() as u64 * tardiness;
+        objectives.late_jobs += (tardiness > 0) as usize;
+    }
+    // max(c + q) - k = max(c - d)
+    if !order.is_empty() {
+        objectives.lmax = Some(objectives.cmax as i64 - k as i64);
+    }
+    objectives
+}
+
+pub fn weighted_completion_time<J: Job>(order: &[J]) -> u64 {
+    order
+        .iter()
+        .zip(completion_times(order))
+        .map(|(job, c)| job.weight() as u64 * c as u64)
+        .sum()
+}
+
+pub fn total_tardiness<J: Job>(order: &[J], k: u32) -> u64 {
+    order
+        .iter()
+        .zip(completion_times(order))
+        .map(|(job, c)| tardiness(job.task(), c, k))
+        .sum()
+}
+
+pub fn late_jobs<J: Job>(order: &[J], k: u32) -> usize {
+    order
+        .iter()
+        .zip(completion_times(order))
+        .filter(|(job, c)| tardiness(job.task(), *c, k) > 0)
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::due_dates::{due_date_horizon, lmax, to_due_dates};
+    use crate::std_heap_impl::schrage_heaps_std;
+    use crate::task::WeightedTask;
+
+    macro_rules! tasks {
+        () => {
+            vec![
+                Task::new(30, 3, 8),
+                Task::new(20, 4, 21),
+                Task::new(10, 5, 7),
+                Task::new(11, 7, 24),
+                Task::new(30, 2, 0),
+                Task::new(13, 6, 26),
+                Task::new(0, 6, 17),
+            ]
+        };
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let tasks = vec![
+            WeightedTask::new(0, 2, 5, 3),
+            WeightedTask::new(1, 3, 1, 1),
+            WeightedTask::new(8, 1, 0, 2),
+        ];
+        // completions 2, 5, 9 and due dates 0, 4, 5
+        let objectives = evaluate(&tasks, 5);
+        assert_eq!(
+            objectives,
+            Objectives {
+                cmax: 9,
+                weighted_completion: 3 * 2 + 5 + 2 * 9,
+                total_tardiness: 2 + 1 + 4,
+                weighted_tardiness: 3 * 2 + 1 + 2 * 4,
+                late_jobs: 3,
+                lmax: Some(4),
+            }
+        );
+        assert_eq!(
+            weighted_completion_time(&tasks),
+            objectives.weighted_completion
+        );
+        assert_eq!(total_tardiness(&tasks, 5), objectives.total_tardiness);
+        assert_eq!(late_jobs(&tasks, 5), objectives.late_jobs);
+
+        // plain tasks count with weight 1
+        let plain: Vec<Task> = tasks.iter().map(|t| t.task).collect();
+        assert_eq!(weighted_completion_time(&plain), 2 + 5 + 9);
+        assert_eq!(evaluate(&plain, 5).weighted_tardiness, 2 + 1 + 4);
+    }
+
+    #[test]
+    fn test_schrage_order() {
+        let (order, cmax) = schrage_heaps_std(tasks!());
+        let k = due_date_horizon(&order);
+        let objectives = evaluate(&order, k);
+        assert_eq!(objectives.cmax, cmax);
+        assert_eq!(objectives.lmax, Some(53 - 26));
+        assert_eq!(evaluate::<Task>(&[], 0).lmax, None);
+        // the same lateness as the due date instance
+        let (due, _) = to_due_dates(&order);
+        assert_eq!(objectives.lmax, lmax(&due));
+    }
+}
@@ -0,0 +1,283 @@
+use crate::schedule::cmax;
+use crate::task::Task;
+use crate::validation::{validate_permutation, ScheduleError};
+use alloc::collections::BinaryHeap;
+use alloc::{vec, vec::Vec};
+use core::cmp::{max, Reverse};
+
+// acyclic precedence relation between tasks identified by their index
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Precedences {
+    successors: Vec<Vec<usize>>,
+    predecessors: Vec<Vec<usize>>,
+    topological: Vec<usize>,
+}
+
+impl Precedences {
+    // (before, after) pairs over n tasks, None when an index is out of range or there is a cycle
+    pub fn new(n: usize, edges: &[(usize, usize)]) -> Option<Precedences> {
+        let mut successors = vec![Vec::new(); n];
+        let mut predecessors = vec![Vec::new(); n];
+        for &(before, after) in edges {
+            if before >= n || after >= n {
+                return None;
+            }
+            successors[before].push(after);
+            predecessors[after].push(before);
+        }
+
+        let mut in_degree: Vec<usize> = predecessors.iter().map(Vec::len).collect();
+        let mut stack: Vec<usize> = (0..n).filter(|&j| in_degree[j] == 0).collect();
+        let mut topological = Vec::with_capacity(n);
+        while let Some(j) = stack.pop() {
+            topological.push(j);
+            for &s in &successors[j] {
+                in_degree[s] -= 1;
+                if in_degree[s] == 0 {
+                    stack.push(s);
+                }
+            }
+        }
+
+        (topological.len() == n).then_some(Precedences {
+            successors,
+            predecessors,
+            topological,
+        })
+    }
+
+    pub fn successors(&self, task: usize) -> &[usize] {
+        &self.successors[task]
+    }
+
+    pub fn predecessors(&self, task: usize) -> &[usize] {
+        &self.predecessors[task]
+    }
+
+    pub fn topological_order(&self) -> &[usize] {
+        &self.topological
+    }
+}
+
+// tasks with an optional precedence relation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instance {
+    pub tasks: Vec<Task>,
+    pub precedences: Option<Precedences>,
+}
+
+impl From<Vec<Task>> for Instance {
+    fn from(tasks: Vec<Task>) -> Self {
+        Instance {
+            tasks,
+            precedences: None,
+        }
+    }
+}
+
+impl Instance {
+    // ready times pushed after the predecessors, q raised to cover the successors,
+    // every order that respects the precedences keeps its cmax
+    pub fn tightened(&self) -> Vec<Task> {
+        let mut tasks = self.tasks.clone();
+        let Some(precedences) = &self.precedences else {
+            return tasks;
+        };
+        for &j in precedences.topological_order() {
+            for &i in precedences.predecessors(j) {
+                tasks[j].r = max(tasks[j].r, tasks[i].r + tasks[i].p);
+            }
+        }
+        for &i in precedences.topological_order().iter().rev() {
+            for &j in precedences.successors(i) {
+                tasks[i].q = max(tasks[i].q, tasks[j].q + tasks[j].p);
+            }
+        }
+        tasks
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PrecedenceViolation {
+    // the order is not a permutation of the tasks
+    InvalidOrder(ScheduleError),
+    Violated {
+        predecessor: usize,
+        successor: usize,
+    },
+}
+
+// order given as task indices, the first violated precedence is reported
+pub fn validate_precedences(
+    order: &[usize],
+    precedences: &Precedences,
+) -> Result<(), PrecedenceViolation> {
+    validate_permutation(precedences.successors.len(), order)
+        .map_err(PrecedenceViolation::InvalidOrder)?;
+    let mut position = vec![0; precedences.successors.len()];
+    for (pos, &task) in order.iter().enumerate() {
+        position[task] = pos;
+    }
+    for (predecessor, successors) in precedences.successors.iter().enumerate() {
+        for &successor in successors {
+            if position[predecessor] >= position[successor] {
+                return Err(PrecedenceViolation::Violated {
+                    predecessor,
+                    successor,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+// schrage on the tightened instance where a task is released only after
+// all of its predecessors are done, returns task indices and cmax
+pub fn schrage_precedence(instance: &Instance) -> (Vec<usize>, u32) {
+    let tasks = instance.tightened();
+    let n = tasks.len();
+    let mut waiting_for: Vec<usize> = match &instance.precedences {
+        Some(precedences) => (0..n).map(|j| precedences.predecessors(j).len()).collect(),
+        None => vec![0; n],
+    };
+
+    // indices are kept in the heaps because equal tasks can have different precedences
+    let mut available_tasks: BinaryHeap<(u32, Reverse<usize>)> = BinaryHeap::new();
+    let mut unavailable_tasks: BinaryHeap<Reverse<(u32, usize)>> = (0..n)
+        .filter(|&j| waiting_for[j] == 0)
+        .map(|j| Reverse((tasks[j].r, j)))
+        .collect();
+    let mut t = 0;
+    let mut order = Vec::with_capacity(n);
+
+    while !available_tasks.is_empty() || !unavailable_tasks.is_empty() {
+        while let Some(&Reverse((r, j))) = unavailable_tasks.peek() {
+            if r > t {
+                break;
+            }
+            unavailable_tasks.pop();
+            available_tasks.push((tasks[j].q, Reverse(j)));
+        }
+        let Some((_, Reverse(j))) = available_tasks.pop() else {
+            t = unavailable_tasks.peek().unwrap().0 .0;
+            continue;
+        };
+
+        t += tasks[j].p;
+        order.push(j);
+        if let Some(precedences) = &instance.precedences {
+            for &s in precedences.successors(j) {
+                waiting_for[s] -= 1;
+                if waiting_for[s] == 0 {
+                    unavailable_tasks.push(Reverse((tasks[s].r, s)));
+                }
+            }
+        }
+    }
+
+    let cmax = cmax(&order.iter().map(|&j| instance.tasks[j]).collect::<Vec<_>>());
+    (order, cmax)
+}
+
+pub fn schrage_precedence_cmax(instance: &Instance) -> u32 {
+    schrage_precedence(instance).1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::std_heap_impl::schrage_heaps_std_cmax;
+
+    macro_rules! tasks {
+        () => {
+            vec![
+                Task::new(30, 3, 8),
+                Task::new(20, 4, 21),
+                Task::new(10, 5, 7),
+                Task::new(11, 7, 24),
+                Task::new(30, 2, 0),
+                Task::new(13, 6, 26),
+                Task::new(0, 6, 17),
+            ]
+        };
+    }
+
+    #[test]
+    fn test_cycle() {
+        assert_eq!(Precedences::new(3, &[(0, 1), (1, 2), (2, 0)]), None);
+        assert_eq!(Precedences::new(2, &[(0, 2)]), None);
+        assert!(Precedences::new(3, &[(0, 1), (0, 2), (1, 2)]).is_some());
+    }
+
+    #[test]
+    fn test_tightened() {
+        let instance = Instance {
+            tasks: vec![Task::new(0, 5, 1), Task::new(2, 3, 4), Task::new(1, 2, 10)],
+            precedences: Precedences::new(3, &[(0, 1), (1, 2)]),
+        };
+        assert_eq!(
+            instance.tightened(),
+            vec![
+                Task::new(0, 5, 15),
+                Task::new(5, 3, 12),
+                Task::new(8, 2, 10)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_without_precedences() {
+        let instance = Instance::from(tasks!());
+        assert_eq!(
+            schrage_precedence_cmax(&instance),
+            schrage_heaps_std_cmax(tasks!())
+        );
+    }
+
+    #[test]
+    fn test_schrage_precedence() {
+        // the task with the largest q has to wait for one released late
+        let precedences = Precedences::new(7, &[(4, 5), (6, 3)]).unwrap();
+        let instance = Instance {
+            tasks: tasks!(),
+            precedences: Some(precedences.clone()),
+        };
+        let (order, cmax) = schrage_precedence(&instance);
+        assert_eq!(validate_precedences(&order, &precedences), Ok(()));
+        // task 5 waits for task 4 released at 30, it runs 32..38 with q = 26
+        assert_eq!(order, vec![6, 2, 3, 1, 4, 5, 0]);
+        assert_eq!(cmax, 64);
+        // tightening does not change the cmax of an order respecting the precedences
+        let tightened = instance.tightened();
+        assert_eq!(
+            cmax,
+            crate::schedule::cmax(&order.iter().map(|&j| tightened[j]).collect::<Vec<_>>())
+        );
+    }
+
+    #[test]
+    fn test_validate_precedences() {
+        let precedences = Precedences::new(3, &[(0, 1), (2, 1)]).unwrap();
+        assert_eq!(validate_precedences(&[0, 2, 1], &precedences), Ok(()));
+        assert_eq!(
+            validate_precedences(&[1, 0, 2], &precedences),
+            Err(PrecedenceViolation::Violated {
+                predecessor: 0,
+                successor: 1
+            })
+        );
+        assert_eq!(
+            validate_precedences(&[0, 3, 1], &precedences),
+            Err(PrecedenceViolation::InvalidOrder(
+                ScheduleError::UnknownTask(3)
+            ))
+        );
+        assert_eq!(
+            validate_precedences(&[0, 1], &precedences),
+            Err(PrecedenceViolation::InvalidOrder(
+                ScheduleError::MissingTask(2)
+            ))
+        );
+    }
+}
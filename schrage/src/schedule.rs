@@ -0,0 +1,163 @@
+use crate::task::Task;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::cmp::max;
+
+// order of tasks on the machine together with its cmax
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Schedule {
+    pub order: Vec<Task>,
+    pub cmax: u32,
+}
+
+impl From<(Vec<Task>, u32)> for Schedule {
+    fn from((order, cmax): (Vec<Task>, u32)) -> Self {
+        Schedule { order, cmax }
+    }
+}
+
+impl From<Vec<Task>> for Schedule {
+    fn from(order: Vec<Task>) -> Self {
+        let cmax = cmax(&order);
+        Schedule { order, cmax }
+    }
+}
+
+impl From<Schedule> for (Vec<Task>, u32) {
+    fn from(schedule: Schedule) -> Self {
+        (schedule.order, schedule.cmax)
+    }
+}
+
+// time at which every task of the order starts, tasks are started as soon as possible
+pub fn start_times(order: &[Task]) -> Vec<u32> {
+    let mut t = 0;
+    order
+        .iter()
+        .map(|task| {
+            let start = max(t, task.r);
+            t = start + task.p;
+            start
+        })
+        .collect()
+}
+
+// cmax of tasks executed in the given order
+pub fn cmax(order: &[Task]) -> u32 {
+    let mut t = 0;
+    let mut cmax = 0;
+    for task in order {
+        t = max(t, task.r) + task.p;
+        cmax = max(cmax, t + task.q);
+    }
+    cmax
+}
+
+// positions in the order of the critical path:
+// a - first task of the block, b - task that determines cmax,
+// c - interference task, the last one in the block with q smaller than q of b
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CriticalPath {
+    pub a: usize,
+    pub b: usize,
+    pub c: Option<usize>,
+}
+
+pub fn critical_path(order: &[Task]) -> Option<CriticalPath> {
+    if order.is_empty() {
+        return None;
+    }
+    let starts = start_times(order);
+    let cmax = cmax(order);
+
+    // last task that reaches cmax
+    let b = (0..order.len())
+        .rev()
+        .find(|&j| starts[j] + order[j].p + order[j].q == cmax)
+        .unwrap();
+
+    // the block starts right after the last idle time before b
+    let mut a = b;
+    while a > 0 && starts[a - 1] + order[a - 1].p == starts[a] {
+        a -= 1;
+    }
+
+    let c = (a..b).rev().find(|&j| order[j].q < order[b].q);
+
+    Some(CriticalPath { a, b, c })
+}
+
+// maps every task of the order to its index in tasks,
+// equal tasks are interchangeable so any of them can be taken
+pub(crate) fn order_indices(order: &[Task], tasks: &[Task]) -> Vec<usize> {
+    let mut positions: BTreeMap<Task, Vec<usize>> = BTreeMap::new();
+    for (idx, task) in tasks.iter().enumerate().rev() {
+        positions.entry(*task).or_default().push(idx);
+    }
+    order
+        .iter()
+        .map(|task| positions.get_mut(task).unwrap().pop().unwrap())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! correct_order {
+        () => {
+            vec![
+                Task::new(0, 6, 17),
+                Task::new(10, 5, 7),
+                Task::new(13, 6, 26),
+                Task::new(11, 7, 24),
+                Task::new(20, 4, 21),
+                Task::new(30, 3, 8),
+                Task::new(30, 2, 0),
+            ]
+        };
+    }
+
+    #[test]
+    fn test_schedule_from_order() {
+        let schedule = Schedule::from(correct_order!());
+        assert_eq!(schedule.cmax, 53);
+        assert_eq!(<(Vec<Task>, u32)>::from(schedule), (correct_order!(), 53));
+    }
+
+    #[test]
+    fn test_cmax() {
+        let order = correct_order!();
+        assert_eq!(cmax(&order), 53);
+        assert_eq!(cmax(&[]), 0);
+    }
+
+    #[test]
+    fn test_start_times() {
+        let order = correct_order!();
+        assert_eq!(start_times(&order), vec![0, 10, 15, 21, 28, 32, 35]);
+    }
+
+    #[test]
+    fn test_critical_path() {
+        let order = correct_order!();
+        let path = critical_path(&order).unwrap();
+        assert_eq!(
+            path,
+            CriticalPath {
+                a: 1,
+                b: 4,
+                c: Some(1)
+            }
+        );
+    }
+
+    #[test]
+    fn test_order_indices() {
+        let tasks = vec![Task::new(1, 1, 1), Task::new(2, 2, 2), Task::new(1, 1, 1)];
+        let order = vec![Task::new(2, 2, 2), Task::new(1, 1, 1), Task::new(1, 1, 1)];
+        assert_eq!(order_indices(&order, &tasks), vec![1, 0, 2]);
+    }
+}
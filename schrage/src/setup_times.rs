@@ -0,0 +1,272 @@
+use crate::schedule::Schedule;
+use crate::task::Task;
+use alloc::vec::Vec;
+use core::cmp::max;
+
+// setup needed on the machine between two tasks, tasks are identified by their index
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Setups {
+    // times[i][j] before task j when it follows task i
+    Matrix(Vec<Vec<u32>>),
+    // every task belongs to a family, setups only depend on the families
+    Families {
+        families: Vec<usize>,
+        times: Vec<Vec<u32>>,
+    },
+}
+
+impl Setups {
+    pub fn between(&self, from: usize, to: usize) -> u32 {
+        match self {
+            Setups::Matrix(times) => times[from][to],
+            Setups::Families { families, times } => times[families[from]][families[to]],
+        }
+    }
+
+    // the first task on the machine needs no setup
+    fn before(&self, previous: Option<usize>, to: usize) -> u32 {
+        previous.map_or(0, |from| self.between(from, to))
+    }
+}
+
+// cmax of tasks executed in the given order (indices into tasks),
+// the setup starts when both the previous task is done and the task is ready
+pub fn setup_cmax(tasks: &[Task], setups: &Setups, order: &[usize]) -> u32 {
+    let mut t = 0;
+    let mut cmax = 0;
+    let mut previous = None;
+    for &idx in order {
+        let task = tasks[idx];
+        t = max(t, task.r) + setups.before(previous, idx) + task.p;
+        cmax = max(cmax, t + task.q);
+        previous = Some(idx);
+    }
+    cmax
+}
+
+// schrage where the setup is paid before every task, of the ready tasks the one with
+// the largest q reduced by its setup after the previous task is chosen
+// the setup changes after every task so the ready tasks are scanned instead of kept in a heap
+// returns indices into tasks, equal tasks can have different setups
+pub fn schrage_setups_indices(tasks: &[Task], setups: &Setups) -> (Vec<usize>, u32) {
+    let mut unavailable: Vec<usize> = (0..tasks.len()).collect();
+    // descending ready times so the next one to release is at the end
+    unavailable.sort_by_key(|&idx| core::cmp::Reverse(tasks[idx].r));
+    let mut available: Vec<usize> = Vec::new();
+    let mut t = 0;
+    let mut cmax = 0;
+    let mut previous = None;
+    let mut order = Vec::with_capacity(tasks.len());
+
+    while !available.is_empty() || !unavailable.is_empty() {
+        while unavailable.last().is_some_and(|&idx| tasks[idx].r <= t) {
+            available.push(unavailable.pop().unwrap());
+        }
+        if available.is_empty() {
+            t = tasks[*unavailable.last().unwrap()].r;
+            continue;
+        }
+
+        let (position, _) = available
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, &idx)| {
+                let setup = setups.before(previous, idx) as i64;
+                (tasks[idx].q as i64 - setup, tasks[idx].q)
+            })
+            .unwrap();
+        let idx = available.swap_remove(position);
+        let task = tasks[idx];
+        t += setups.before(previous, idx) + task.p;
+        cmax = max(cmax, t + task.q);
+        order.push(idx);
+        previous = Some(idx);
+    }
+
+    (order, cmax)
+}
+
+pub fn schrage_setups(tasks: &[Task], setups: &Setups) -> Schedule {
+    let (order, cmax) = schrage_setups_indices(tasks, setups);
+    Schedule {
+        order: order.into_iter().map(|idx| tasks[idx]).collect(),
+        cmax,
+    }
+}
+
+pub fn schrage_setups_cmax(tasks: &[Task], setups: &Setups) -> u32 {
+    schrage_setups_indices(tasks, setups).1
+}
+
+// instance format: number of tasks, a "r p q" line per task and then either
+// "matrix:" with a row of setups per task, or "families:" with the family of every task
+// in one line followed by a row of setups per family
+pub fn parse_setup_instance(input: &str) -> Option<(Vec<Task>, Setups)> {
+    let mut lines = input.lines().map(str::trim).filter(|l| !l.is_empty());
+    let numbers = |line: &str| -> Option<Vec<u32>> {
+        line.split_whitespace().map(|w| w.parse().ok()).collect()
+    };
+
+    let n: usize = lines.next()?.parse().ok()?;
+    let mut tasks = Vec::with_capacity(n);
+    for _ in 0..n {
+        let [r, p, q] = numbers(lines.next()?)?[..] else {
+            return None;
+        };
+        tasks.push(Task::new(r, p, q));
+    }
+
+    let setups = match lines.next()? {
+        "matrix:" => {
+            let times: Vec<Vec<u32>> = (0..n)
+                .map(|_| numbers(lines.next()?).filter(|row| row.len() == n))
+                .collect::<Option<_>>()?;
+            Setups::Matrix(times)
+        }
+        "families:" => {
+            let families: Vec<usize> = numbers(lines.next()?)?
+                .into_iter()
+                .map(|f| f as usize)
+                .collect();
+            let count = families.iter().max().map_or(0, |f| f + 1);
+            if families.len() != n {
+                return None;
+            }
+            let times: Vec<Vec<u32>> = (0..count)
+                .map(|_| numbers(lines.next()?).filter(|row| row.len() == count))
+                .collect::<Option<_>>()?;
+            Setups::Families { families, times }
+        }
+        _ => return None,
+    };
+
+    Some((tasks, setups))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::std_heap_impl::schrage_heaps_std_cmax;
+    use std::fs::File;
+    use std::io::Read;
+    use std::path::PathBuf;
+
+    macro_rules! tasks {
+        () => {
+            vec![
+                Task::new(30, 3, 8),
+                Task::new(20, 4, 21),
+                Task::new(10, 5, 7),
+                Task::new(11, 7, 24),
+                Task::new(30, 2, 0),
+                Task::new(13, 6, 26),
+                Task::new(0, 6, 17),
+            ]
+        };
+    }
+
+    // instances of the setup test file separated by "data.xxx:" lines,
+    // each followed by its optimal "opt:" cmax, checked by enumerating the orders
+    fn parse_test_file(filename: &str) -> Vec<(Vec<Task>, Setups, u32)> {
+        let mut file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        file_path.push(filename);
+        let mut buf = String::new();
+        File::open(&file_path)
+            .unwrap()
+            .read_to_string(&mut buf)
+            .unwrap();
+
+        buf.split("data.")
+            .skip(1)
+            .map(|block| {
+                let (instance, expected) = block.split_once("opt:").unwrap();
+                let instance = instance.split_once(':').unwrap().1;
+                let (tasks, setups) = parse_setup_instance(instance).unwrap();
+                (tasks, setups, expected.trim().parse().unwrap())
+            })
+            .collect()
+    }
+
+    // checks every permutation, only for tiny instances
+    fn brute_force_cmax(tasks: &[Task], setups: &Setups, order: &mut Vec<usize>, k: usize) -> u32 {
+        if k == order.len() {
+            return setup_cmax(tasks, setups, order);
+        }
+        let mut best = u32::MAX;
+        for i in k..order.len() {
+            order.swap(k, i);
+            best = best.min(brute_force_cmax(tasks, setups, order, k + 1));
+            order.swap(k, i);
+        }
+        best
+    }
+
+    #[test]
+    fn test_zero_setups() {
+        let setups = Setups::Matrix(vec![vec![0; 7]; 7]);
+        let tasks = tasks!();
+        assert_eq!(
+            schrage_setups_cmax(&tasks, &setups),
+            schrage_heaps_std_cmax(tasks!())
+        );
+    }
+
+    #[test]
+    fn test_setup_cmax() {
+        let tasks = vec![Task::new(0, 2, 1), Task::new(0, 3, 0), Task::new(10, 1, 1)];
+        let setups = Setups::Families {
+            families: vec![0, 1, 0],
+            times: vec![vec![0, 4], vec![2, 0]],
+        };
+        // 0..2, setup 4, 6..9, waits for 10, setup 2, 12..13
+        assert_eq!(setup_cmax(&tasks, &setups, &[0, 1, 2]), 14);
+        // 0..3, setup 2, 5..7, waits for 10, no setup, 10..11
+        assert_eq!(setup_cmax(&tasks, &setups, &[1, 0, 2]), 12);
+    }
+
+    #[test]
+    fn test_setups_change_the_choice() {
+        // without setups the task with q = 5 would go second
+        let tasks = vec![Task::new(0, 1, 0), Task::new(1, 1, 5), Task::new(1, 1, 4)];
+        let mut times = vec![vec![0; 3]; 3];
+        times[0][1] = 10;
+        let setups = Setups::Matrix(times);
+        let (order, cmax) = schrage_setups_indices(&tasks, &setups);
+        assert_eq!(order, vec![0, 2, 1]);
+        assert_eq!(cmax, setup_cmax(&tasks, &setups, &order));
+        // 0..1, 1..2, 2..3 with q = 5
+        assert_eq!(cmax, 8);
+        assert_eq!(
+            schrage_setups(&tasks, &setups),
+            Schedule {
+                order: vec![tasks[0], tasks[2], tasks[1]],
+                cmax: 8
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_setup_instance() {
+        let (tasks, setups) = parse_setup_instance("2\n0 1 2\n3 4 5\nfamilies:\n0 0\n7\n").unwrap();
+        assert_eq!(tasks, vec![Task::new(0, 1, 2), Task::new(3, 4, 5)]);
+        assert_eq!(setups.between(0, 1), 7);
+        assert_eq!(parse_setup_instance("1\n0 1 2\nmatrix:\n1 2\n"), None);
+    }
+
+    #[test]
+    fn test_setup_file() {
+        let instances = parse_test_file("test_data/setup.data");
+        assert_eq!(instances.len(), 2);
+        for (tasks, setups, optimum) in instances {
+            let mut order: Vec<usize> = (0..tasks.len()).collect();
+            assert_eq!(brute_force_cmax(&tasks, &setups, &mut order, 0), optimum);
+
+            let (order, cmax) = schrage_setups_indices(&tasks, &setups);
+            assert_eq!(setup_cmax(&tasks, &setups, &order), cmax);
+            assert!(cmax >= optimum);
+            let mut sorted = order.clone();
+            sorted.sort();
+            assert_eq!(sorted, (0..tasks.len()).collect::<Vec<_>>());
+        }
+    }
+}
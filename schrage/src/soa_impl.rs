@@ -0,0 +1,356 @@
+use crate::task::Task;
+use alloc::vec::Vec;
+use core::cmp::{max, Reverse};
+use core::marker::PhantomData;
+
+// instance stored as separate r, p, q arrays, tasks are referred to by u32 indices
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TaskArrays {
+    pub r: Vec<u32>,
+    pub p: Vec<u32>,
+    pub q: Vec<u32>,
+}
+
+impl TaskArrays {
+    pub fn len(&self) -> usize {
+        self.r.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.r.is_empty()
+    }
+
+    pub fn task(&self, idx: u32) -> Task {
+        let idx = idx as usize;
+        Task::new(self.r[idx], self.p[idx], self.q[idx])
+    }
+}
+
+impl From<&[Task]> for TaskArrays {
+    fn from(tasks: &[Task]) -> Self {
+        assert!(
+            u32::try_from(tasks.len()).is_ok(),
+            "too many tasks for u32 indices"
+        );
+        TaskArrays {
+            r: tasks.iter().map(|t| t.r).collect(),
+            p: tasks.iter().map(|t| t.p).collect(),
+            q: tasks.iter().map(|t| t.q).collect(),
+        }
+    }
+}
+
+impl From<Vec<Task>> for TaskArrays {
+    fn from(tasks: Vec<Task>) -> Self {
+        TaskArrays::from(tasks.as_slice())
+    }
+}
+
+// key of an index in an IndexHeap, the index with the greatest key is on top
+pub trait IndexKey {
+    type Key: Ord + Copy;
+
+    fn key(tasks: &TaskArrays, idx: u32) -> Self::Key;
+}
+
+// smallest r on top, like RInvariant
+#[derive(Debug)]
+pub struct ByR;
+
+// largest q on top, like QInvariant
+#[derive(Debug)]
+pub struct ByQ;
+
+impl IndexKey for ByR {
+    type Key = Reverse<u32>;
+
+    fn key(tasks: &TaskArrays, idx: u32) -> Reverse<u32> {
+        Reverse(tasks.r[idx as usize])
+    }
+}
+
+impl IndexKey for ByQ {
+    type Key = u32;
+
+    fn key(tasks: &TaskArrays, idx: u32) -> u32 {
+        tasks.q[idx as usize]
+    }
+}
+
+// binary max heap of task indices, the keys are looked up in the arrays,
+// sifting is done the same way as in std BinaryHeap so ties between equal keys
+// are resolved the same and schedules match schrage_heaps_std
+#[derive(Debug)]
+pub struct IndexHeap<K> {
+    data: Vec<u32>,
+    key: PhantomData<K>,
+}
+
+impl<K> Default for IndexHeap<K> {
+    fn default() -> Self {
+        IndexHeap {
+            data: Vec::new(),
+            key: PhantomData,
+        }
+    }
+}
+
+impl<K: IndexKey> IndexHeap<K> {
+    pub fn new() -> IndexHeap<K> {
+        IndexHeap::default()
+    }
+
+    pub fn with_capacity(n: usize) -> IndexHeap<K> {
+        IndexHeap {
+            data: Vec::with_capacity(n),
+            key: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    // indices in the order of the heap array, the top one first
+    pub fn as_slice(&self) -> &[u32] {
+        &self.data
+    }
+
+    pub fn peek(&self) -> Option<u32> {
+        self.data.first().copied()
+    }
+
+    pub fn push(&mut self, tasks: &TaskArrays, idx: u32) {
+        self.data.push(idx);
+        self.sift_up(tasks, 0, self.data.len() - 1);
+    }
+
+    pub fn pop(&mut self, tasks: &TaskArrays) -> Option<u32> {
+        let mut top = self.data.pop()?;
+        if !self.data.is_empty() {
+            core::mem::swap(&mut top, &mut self.data[0]);
+            self.sift_down_to_bottom(tasks, 0);
+        }
+        Some(top)
+    }
+
+    // the heap is replaced by all indices of the arrays
+    pub fn fill(&mut self, tasks: &TaskArrays) {
+        self.data.clear();
+        self.data.extend(0..tasks.len() as u32);
+        let mut n = self.data.len() / 2;
+        while n > 0 {
+            n -= 1;
+            self.sift_down(tasks, n);
+        }
+    }
+
+    fn key(&self, tasks: &TaskArrays, pos: usize) -> K::Key {
+        K::key(tasks, self.data[pos])
+    }
+
+    // the key of the moved element is looked up once for the whole sift
+    fn sift_up(&mut self, tasks: &TaskArrays, start: usize, mut pos: usize) -> usize {
+        let element = self.data[pos];
+        let key = K::key(tasks, element);
+        while pos > start {
+            let parent = (pos - 1) / 2;
+            if key <= self.key(tasks, parent) {
+                break;
+            }
+            self.data[pos] = self.data[parent];
+            pos = parent;
+        }
+        self.data[pos] = element;
+        pos
+    }
+
+    fn sift_down(&mut self, tasks: &TaskArrays, mut pos: usize) {
+        let end = self.data.len();
+        let element = self.data[pos];
+        let key = K::key(tasks, element);
+        let mut child = 2 * pos + 1;
+        while child <= end.saturating_sub(2) {
+            child += (self.key(tasks, child) <= self.key(tasks, child + 1)) as usize;
+            if key >= self.key(tasks, child) {
+                self.data[pos] = element;
+                return;
+            }
+            self.data[pos] = self.data[child];
+            pos = child;
+            child = 2 * pos + 1;
+        }
+        if child == end - 1 && key < self.key(tasks, child) {
+            self.data[pos] = self.data[child];
+            pos = child;
+        }
+        self.data[pos] = element;
+    }
+
+    // the hole goes all the way down and the element is sifted back up,
+    // fewer comparisons as the last element usually belongs near the bottom
+    fn sift_down_to_bottom(&mut self, tasks: &TaskArrays, mut pos: usize) {
+        let end = self.data.len();
+        let start = pos;
+        let element = self.data[pos];
+        let mut child = 2 * pos + 1;
+        while child <= end.saturating_sub(2) {
+            child += (self.key(tasks, child) <= self.key(tasks, child + 1)) as usize;
+            self.data[pos] = self.data[child];
+            pos = child;
+            child = 2 * pos + 1;
+        }
+        if child == end - 1 {
+            self.data[pos] = self.data[child];
+            pos = child;
+        }
+        self.data[pos] = element;
+        self.sift_up(tasks, start, pos);
+    }
+}
+
+// the two index heaps of schrage kept between runs
+#[derive(Debug, Default)]
+pub struct SoaScratch {
+    available_tasks: IndexHeap<ByQ>,
+    unavailable_tasks: IndexHeap<ByR>,
+}
+
+impl SoaScratch {
+    pub fn new() -> SoaScratch {
+        SoaScratch::default()
+    }
+
+    // indices of tasks in the order they are scheduled are written into the buffer
+    pub fn schrage_into(&mut self, tasks: &TaskArrays, order: &mut Vec<u32>) -> u32 {
+        order.clear();
+        self.run(tasks, |idx| order.push(idx))
+    }
+
+    pub fn schrage_cmax(&mut self, tasks: &TaskArrays) -> u32 {
+        self.run(tasks, |_| {})
+    }
+
+    fn run(&mut self, tasks: &TaskArrays, mut scheduled: impl FnMut(u32)) -> u32 {
+        let available_tasks = &mut self.available_tasks;
+        let unavailable_tasks = &mut self.unavailable_tasks;
+        available_tasks.clear();
+        unavailable_tasks.fill(tasks);
+        let mut t = 0;
+        let mut cmax = 0;
+
+        while !available_tasks.is_empty() || !unavailable_tasks.is_empty() {
+            while let Some(idx) = unavailable_tasks.peek() {
+                if tasks.r[idx as usize] > t {
+                    break;
+                }
+                unavailable_tasks.pop(tasks);
+                available_tasks.push(tasks, idx);
+            }
+
+            let Some(idx) = available_tasks.pop(tasks) else {
+                t = tasks.r[unavailable_tasks.peek().unwrap() as usize];
+                continue;
+            };
+            t += tasks.p[idx as usize];
+            cmax = max(cmax, t + tasks.q[idx as usize]);
+            scheduled(idx);
+        }
+
+        cmax
+    }
+}
+
+pub fn schrage_soa_heaps_indices(tasks: &TaskArrays) -> (Vec<u32>, u32) {
+    let mut order = Vec::with_capacity(tasks.len());
+    let cmax = SoaScratch::new().schrage_into(tasks, &mut order);
+    (order, cmax)
+}
+
+pub fn schrage_soa_heaps_arrays_cmax(tasks: &TaskArrays) -> u32 {
+    SoaScratch::new().schrage_cmax(tasks)
+}
+
+pub fn schrage_soa_heaps(tasks: Vec<Task>) -> (Vec<Task>, u32) {
+    let (order, cmax) = schrage_soa_heaps_indices(&TaskArrays::from(tasks.as_slice()));
+    (
+        order.into_iter().map(|idx| tasks[idx as usize]).collect(),
+        cmax,
+    )
+}
+
+pub fn schrage_soa_heaps_cmax(tasks: Vec<Task>) -> u32 {
+    schrage_soa_heaps_arrays_cmax(&TaskArrays::from(tasks))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::Generator;
+    use crate::std_heap_impl::schrage_heaps_std;
+
+    macro_rules! tasks {
+        () => {
+            vec![
+                Task::new(30, 3, 8),
+                Task::new(20, 4, 21),
+                Task::new(10, 5, 7),
+                Task::new(11, 7, 24),
+                Task::new(30, 2, 0),
+                Task::new(13, 6, 26),
+                Task::new(0, 6, 17),
+            ]
+        };
+    }
+
+    #[test]
+    fn test_schrage_soa_heaps() {
+        assert_eq!(schrage_soa_heaps(tasks!()), schrage_heaps_std(tasks!()));
+        assert_eq!(schrage_soa_heaps_cmax(tasks!()), 53);
+        assert_eq!(schrage_soa_heaps(vec![]), (vec![], 0));
+    }
+
+    #[test]
+    fn test_index_heap() {
+        let tasks = TaskArrays::from(tasks!());
+        let mut by_r: IndexHeap<ByR> = IndexHeap::new();
+        by_r.fill(&tasks);
+        let mut by_q: IndexHeap<ByQ> = IndexHeap::with_capacity(tasks.len());
+        let mut r = Vec::new();
+        while let Some(idx) = by_r.pop(&tasks) {
+            r.push(tasks.r[idx as usize]);
+            by_q.push(&tasks, idx);
+        }
+        assert_eq!(r, vec![0, 10, 11, 13, 20, 30, 30]);
+        let q: Vec<u32> = std::iter::from_fn(|| by_q.pop(&tasks))
+            .map(|idx| tasks.q[idx as usize])
+            .collect();
+        assert_eq!(q, vec![26, 24, 21, 17, 8, 7, 0]);
+    }
+
+    // narrow ranges give lots of equal keys, the orders only match
+    // when ties are broken exactly as in BinaryHeap
+    #[test]
+    fn test_same_orders_as_std_heaps() {
+        let mut generator = Generator::new(5);
+        let mut scratch = SoaScratch::new();
+        let mut order = Vec::new();
+        for n in 0..300 {
+            let tasks = generator.uniform(n, &(0..(n as u32 / 4 + 1)), &(1..5), &(0..4));
+            let expected = schrage_heaps_std(tasks.clone());
+            assert_eq!(schrage_soa_heaps(tasks.clone()), expected);
+
+            let arrays = TaskArrays::from(tasks.as_slice());
+            assert_eq!(scratch.schrage_into(&arrays, &mut order), expected.1);
+            assert_eq!(scratch.schrage_cmax(&arrays), expected.1);
+        }
+    }
+}
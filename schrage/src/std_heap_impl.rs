@@ -1,100 +1,318 @@
-use crate::task::{QInvariant, RInvariant, Task};
-use std::cmp::max;
-use std::collections::BinaryHeap;
+use crate::task::{Entry, Job, KeyInvariant, QInvariant, RInvariant, Ratio, Task};
+use crate::validation::Segment;
+use alloc::collections::BinaryHeap;
+use alloc::vec::Vec;
+use core::cmp::{max, min, Reverse};
+use heap::{CapacityError, Heap};
 
-pub fn schrage_heaps_std(tasks: Vec<Task>) -> (Vec<Task>, u32) {
-    let mut available_tasks: BinaryHeap<QInvariant> = BinaryHeap::new();
-    let mut unavailable_tasks: BinaryHeap<RInvariant> = tasks.iter().map(|t| t.into()).collect();
+// dispatch rules, the ready task with the largest key is scheduled next
+
+// classic schrage rule, the largest cooldown time
+pub fn largest_q(task: &Task) -> u32 {
+    task.q
+}
+
+pub fn largest_q_plus_p(task: &Task) -> u64 {
+    task.q as u64 + task.p as u64
+}
+
+pub fn smallest_p(task: &Task) -> Reverse<u32> {
+    Reverse(task.p)
+}
+
+pub fn largest_q_over_p(task: &Task) -> Ratio {
+    Ratio::new(task.q, task.p)
+}
+
+// the event driven loop shared by every dispatch rule and heap storage,
+// unavailable_tasks holds the jobs that are not released yet with the smallest r on top,
+// ready wraps a released job with its key and the largest one is scheduled next,
+// every scheduled job is passed on to scheduled
+pub fn schrage_loop<J, R, U>(
+    available_tasks: &mut impl Heap<R>,
+    unavailable_tasks: &mut impl Heap<U>,
+    ready: impl Fn(J) -> R,
+    mut scheduled: impl FnMut(J),
+) -> Result<u32, CapacityError<J>>
+where
+    J: Job,
+    R: Entry<J>,
+    U: Entry<J>,
+{
     let mut t = 0;
     let mut cmax = 0;
-    let mut order = Vec::new();
 
     // heaps make code cleaner and more imperative
     while !available_tasks.is_empty() || !unavailable_tasks.is_empty() {
-        // unwrap is safe beacause the while loop condition
-        while !unavailable_tasks.is_empty() && unavailable_tasks.peek().unwrap().0.r <= t {
-            let task = unavailable_tasks.pop().unwrap().0;
-            available_tasks.push(task.into());
+        while unavailable_tasks
+            .peek()
+            .is_some_and(|entry| entry.job().task().r <= t)
+        {
+            // unwrap is safe, peek found the job
+            let job = unavailable_tasks.pop().unwrap().into_job();
+            available_tasks
+                .push(ready(job))
+                .map_err(|e| CapacityError(e.0.into_job()))?;
         }
-        if available_tasks.is_empty() {
+
+        let Some(entry) = available_tasks.pop() else {
             // unwrap is safe, if the available_tasks is empty
             // then the unavailable_tasks is not empty
             // because of the while loop condition
-            t = unavailable_tasks.peek().unwrap().0.r;
+            t = unavailable_tasks.peek().unwrap().job().task().r;
             continue;
-        }
-
-        let task = available_tasks.pop().unwrap().0;
-        t += task.p;
-        cmax = max(cmax, t + task.q);
-        order.push(task);
+        };
+        let job = entry.into_job();
+        t += job.task().p;
+        cmax = max(cmax, t + job.task().q);
+        scheduled(job);
     }
 
-    (order, cmax)
+    Ok(cmax)
 }
 
-// just cmax
-pub fn schrage_heaps_std_cmax(tasks: Vec<Task>) -> u32 {
-    let mut available_tasks: BinaryHeap<QInvariant> = BinaryHeap::new();
-    let mut unavailable_tasks: BinaryHeap<RInvariant> = tasks.iter().map(|t| t.into()).collect();
+// preemptive version of schrage_loop, a released task with a larger key
+// than the running one takes the machine over
+pub fn schrage_preemptive_loop<R, U>(
+    available_tasks: &mut impl Heap<R>,
+    unavailable_tasks: &mut impl Heap<U>,
+    ready: impl Fn(Task) -> R,
+) -> Result<u32, CapacityError<Task>>
+where
+    R: Entry<Task> + Ord,
+    U: Entry<Task>,
+{
     let mut t = 0;
     let mut cmax = 0;
+    let mut current_task: Option<Task> = None;
 
     while !available_tasks.is_empty() || !unavailable_tasks.is_empty() {
-        while !unavailable_tasks.is_empty() && unavailable_tasks.peek().unwrap().0.r <= t {
-            let task = unavailable_tasks.pop().unwrap().0;
-            available_tasks.push(task.into());
+        while unavailable_tasks
+            .peek()
+            .is_some_and(|entry| entry.job().r <= t)
+        {
+            let task = unavailable_tasks.pop().unwrap().into_job();
+            available_tasks
+                .push(ready(task))
+                .map_err(|e| CapacityError(e.0.into_job()))?;
+            if let Some(ref mut current) = current_task {
+                if ready(task) > ready(*current) {
+                    current.p = t - task.r;
+                    t = task.r;
+                    if current.p > 0 {
+                        available_tasks
+                            .push(ready(*current))
+                            .map_err(|e| CapacityError(e.0.into_job()))?;
+                    }
+                }
+            }
         }
 
-        if available_tasks.is_empty() {
-            t = unavailable_tasks.peek().unwrap().0.r;
+        let Some(entry) = available_tasks.pop() else {
+            t = unavailable_tasks.peek().unwrap().job().r;
+            current_task = None;
             continue;
+        };
+        let task_to_do = entry.into_job();
+        t += task_to_do.p;
+        cmax = max(cmax, t + task_to_do.q);
+        current_task = Some(task_to_do);
+    }
+
+    Ok(cmax)
+}
+
+// jobs with their release times as keys, the smallest r on top
+fn unavailable_by_key<J: Job>(tasks: &[J]) -> BinaryHeap<KeyInvariant<Reverse<u32>, J>> {
+    tasks
+        .iter()
+        .map(|&job| KeyInvariant(Reverse(job.task().r), job))
+        .collect()
+}
+
+// schrage with the dispatch rule given by key, the ready job with the largest key
+// is scheduled next
+pub fn schrage_heaps_std_by_key<J: Job, K: Ord>(
+    tasks: Vec<J>,
+    key: impl Fn(&J) -> K,
+) -> (Vec<J>, u32) {
+    let mut order = Vec::with_capacity(tasks.len());
+    let cmax = schrage_heaps_std_by_key_with(&tasks, key, |job| order.push(job));
+    (order, cmax)
+}
+
+pub fn schrage_heaps_std_by_key_cmax<J: Job, K: Ord>(tasks: Vec<J>, key: impl Fn(&J) -> K) -> u32 {
+    schrage_heaps_std_by_key_with(&tasks, key, |_| {})
+}
+
+fn schrage_heaps_std_by_key_with<J: Job, K: Ord>(
+    tasks: &[J],
+    key: impl Fn(&J) -> K,
+    scheduled: impl FnMut(J),
+) -> u32 {
+    let mut available_tasks: BinaryHeap<KeyInvariant<K, J>> = BinaryHeap::new();
+    let mut unavailable_tasks = unavailable_by_key(tasks);
+    schrage_loop(
+        &mut available_tasks,
+        &mut unavailable_tasks,
+        |job| KeyInvariant(key(&job), job),
+        scheduled,
+    )
+    .unwrap_or_else(|_| unreachable!("std heaps grow"))
+}
+
+pub fn schrage_preemptive_heaps_std_by_key_cmax<K: Ord>(
+    tasks: Vec<Task>,
+    key: impl Fn(&Task) -> K,
+) -> u32 {
+    let mut available_tasks: BinaryHeap<KeyInvariant<K>> = BinaryHeap::new();
+    let mut unavailable_tasks = unavailable_by_key(&tasks);
+    // unwrap is safe, std heaps grow
+    schrage_preemptive_loop(&mut available_tasks, &mut unavailable_tasks, |task| {
+        KeyInvariant(key(&task), task)
+    })
+    .unwrap()
+}
+
+// the classic rule, the largest cooldown time
+pub fn schrage_heaps_std(tasks: Vec<Task>) -> (Vec<Task>, u32) {
+    schrage_heaps_std_by_key(tasks, largest_q)
+}
+
+// the two heaps of schrage kept between runs, repeated solves on instances
+// of similar size do not allocate
+#[derive(Debug, Default)]
+pub struct SchrageScratch {
+    available_tasks: BinaryHeap<QInvariant>,
+    unavailable_tasks: BinaryHeap<RInvariant>,
+}
+
+impl SchrageScratch {
+    pub fn new() -> SchrageScratch {
+        SchrageScratch::default()
+    }
+
+    pub fn with_capacity(n: usize) -> SchrageScratch {
+        SchrageScratch {
+            available_tasks: BinaryHeap::with_capacity(n),
+            unavailable_tasks: BinaryHeap::with_capacity(n),
         }
+    }
+
+    fn fill(&mut self, tasks: &[Task]) {
+        self.available_tasks.clear();
+        self.unavailable_tasks.clear();
+        self.unavailable_tasks
+            .extend(tasks.iter().map(RInvariant::from));
+    }
+
+    // the one loop of the scratch, every scheduled task is passed on to scheduled
+    fn run(&mut self, tasks: &[Task], scheduled: impl FnMut(Task)) -> u32 {
+        self.fill(tasks);
+        schrage_loop(
+            &mut self.available_tasks,
+            &mut self.unavailable_tasks,
+            QInvariant,
+            scheduled,
+        )
+        .unwrap_or_else(|_| unreachable!("std heaps grow"))
+    }
 
-        let task = available_tasks.pop().unwrap().0;
-        t += task.p;
-        cmax = max(cmax, t + task.q);
+    // the order is written into the buffer, previous contents are dropped
+    pub fn schrage_into(&mut self, tasks: &[Task], order: &mut Vec<Task>) -> u32 {
+        order.clear();
+        self.run(tasks, |task| order.push(task))
     }
 
-    cmax
+    // just cmax
+    pub fn schrage_cmax(&mut self, tasks: &[Task]) -> u32 {
+        self.run(tasks, |_| {})
+    }
+
+    pub fn schrage_preemptive_cmax(&mut self, tasks: &[Task]) -> u32 {
+        self.fill(tasks);
+        schrage_preemptive_loop(
+            &mut self.available_tasks,
+            &mut self.unavailable_tasks,
+            QInvariant,
+        )
+        .unwrap_or_else(|_| unreachable!("std heaps grow"))
+    }
+}
+
+// entry points that borrow the instance
+pub fn schrage_heaps_std_slice(tasks: &[Task]) -> (Vec<Task>, u32) {
+    let mut order = Vec::with_capacity(tasks.len());
+    let cmax = schrage_heaps_std_into(tasks, &mut order);
+    (order, cmax)
+}
+
+pub fn schrage_heaps_std_into(tasks: &[Task], order: &mut Vec<Task>) -> u32 {
+    SchrageScratch::with_capacity(tasks.len()).schrage_into(tasks, order)
+}
+
+pub fn schrage_heaps_std_slice_cmax(tasks: &[Task]) -> u32 {
+    SchrageScratch::with_capacity(tasks.len()).schrage_cmax(tasks)
+}
+
+pub fn schrage_preemptive_heaps_std_slice_cmax(tasks: &[Task]) -> u32 {
+    SchrageScratch::with_capacity(tasks.len()).schrage_preemptive_cmax(tasks)
+}
+
+// just cmax
+pub fn schrage_heaps_std_cmax(tasks: Vec<Task>) -> u32 {
+    schrage_heaps_std_by_key_cmax(tasks, largest_q)
 }
 
 pub fn schrage_preemptive_heaps_std_cmax(tasks: Vec<Task>) -> u32 {
-    let mut available_tasks: BinaryHeap<QInvariant> = BinaryHeap::new();
-    let mut unavailable_tasks: BinaryHeap<RInvariant> = tasks.iter().map(|t| t.into()).collect();
+    schrage_preemptive_heaps_std_by_key_cmax(tasks, largest_q)
+}
+
+// preemptive schedule as pieces of work on the given tasks, the index of a task
+// is kept in the heaps so that the pieces of one task can be told apart from equal tasks
+pub fn schrage_preemptive_segments(tasks: &[Task]) -> (Vec<Segment>, u32) {
+    let mut available_tasks: BinaryHeap<(u32, Reverse<usize>)> = BinaryHeap::new();
+    let mut unavailable_tasks: BinaryHeap<Reverse<(u32, usize)>> = tasks
+        .iter()
+        .enumerate()
+        .map(|(j, task)| Reverse((task.r, j)))
+        .collect();
+    let mut remaining: Vec<u32> = tasks.iter().map(|t| t.p).collect();
+    let mut segments: Vec<Segment> = Vec::new();
     let mut t = 0;
     let mut cmax = 0;
 
-    let mut current_task: Option<Task> = None;
-
     while !available_tasks.is_empty() || !unavailable_tasks.is_empty() {
-        while !unavailable_tasks.is_empty() && unavailable_tasks.peek().unwrap().0.r <= t {
-            let task: Task = unavailable_tasks.pop().unwrap().into();
-            available_tasks.push(task.into());
-            if let Some(ref mut current) = current_task {
-                if task.q > current.q {
-                    current.p = t - task.r;
-                    t = task.r;
-                    if current.p > 0 {
-                        available_tasks.push(current.to_owned().into())
-                    }
-                }
+        while let Some(&Reverse((r, j))) = unavailable_tasks.peek() {
+            if r > t {
+                break;
             }
+            unavailable_tasks.pop();
+            available_tasks.push((tasks[j].q, Reverse(j)));
         }
-
-        if available_tasks.is_empty() {
-            t = unavailable_tasks.peek().unwrap().0.r;
-            current_task = None;
+        let Some(&(q, Reverse(j))) = available_tasks.peek() else {
+            t = unavailable_tasks.peek().unwrap().0 .0;
             continue;
-        }
+        };
 
-        let task_to_do = available_tasks.pop().unwrap().0;
-        t += task_to_do.p;
-        cmax = max(cmax, t + task_to_do.q);
-        current_task = Some(task_to_do);
+        // the task runs until it is done or until the next release
+        let end = match unavailable_tasks.peek() {
+            Some(&Reverse((r, _))) => min(t + remaining[j], r),
+            None => t + remaining[j],
+        };
+        remaining[j] -= end - t;
+        match segments.last_mut() {
+            Some(last) if last.task == j && last.end == t => last.end = end,
+            _ => segments.push(Segment::new(j, t, end)),
+        }
+        t = end;
+        if remaining[j] == 0 {
+            available_tasks.pop();
+            cmax = max(cmax, t + q);
+        }
     }
 
-    cmax
+    (segments, cmax)
 }
 
 #[cfg(test)]
@@ -143,6 +361,99 @@ mod tests {
         assert_eq!(order, correct_order!());
     }
 
+    #[test]
+    fn test_slices_and_scratch() {
+        let tasks = tasks!();
+        assert_eq!(schrage_heaps_std_slice(&tasks), (correct_order!(), 53));
+        assert_eq!(schrage_heaps_std_slice_cmax(&tasks), 53);
+        assert_eq!(schrage_preemptive_heaps_std_slice_cmax(&tasks), 49);
+
+        // the buffer and the heaps are reused, stale contents do not leak into the result
+        let mut scratch = SchrageScratch::new();
+        let mut order = vec![Task::new(1, 1, 1)];
+        for _ in 0..2 {
+            assert_eq!(scratch.schrage_into(&tasks, &mut order), 53);
+            assert_eq!(order, correct_order!());
+            assert_eq!(
+                scratch.schrage_cmax(&tasks[..3]),
+                schrage_heaps_std_cmax(tasks[..3].to_vec())
+            );
+            assert_eq!(scratch.schrage_preemptive_cmax(&tasks), 49);
+        }
+    }
+
+    #[test]
+    fn test_preemptive_segments() {
+        let tasks = tasks!();
+        let (segments, cmax) = schrage_preemptive_segments(&tasks);
+        assert_eq!(cmax, schrage_preemptive_heaps_std_cmax(tasks!()));
+        assert_eq!(
+            crate::validation::validate_segments(&tasks, &segments),
+            Ok(cmax)
+        );
+    }
+
+    #[test]
+    fn test_dispatch_rules() {
+        assert_eq!(schrage_heaps_std_by_key_cmax(tasks!(), largest_q), 53);
+        let (order, cmax) = schrage_heaps_std_by_key(tasks!(), smallest_p);
+        assert_eq!(order[0], Task::new(0, 6, 17));
+        assert_eq!(order[1], Task::new(10, 5, 7));
+        assert_eq!(cmax, crate::schedule::cmax(&order));
+        for cmax in [
+            schrage_heaps_std_by_key_cmax(tasks!(), largest_q_plus_p),
+            schrage_heaps_std_by_key_cmax(tasks!(), largest_q_over_p),
+            schrage_heaps_std_by_key_cmax(tasks!(), |t: &Task| (t.q, Reverse(t.p))),
+        ] {
+            assert!(cmax >= 50);
+        }
+        // the classic key gives the classic preemptive schrage
+        assert_eq!(
+            schrage_preemptive_heaps_std_by_key_cmax(tasks!(), largest_q),
+            49
+        );
+        assert_eq!(
+            schrage_preemptive_heaps_std_by_key_cmax(tasks!(), largest_q),
+            schrage_preemptive_heaps_std_slice_cmax(&tasks!()[..])
+        );
+        assert!(schrage_preemptive_heaps_std_by_key_cmax(tasks!(), largest_q_plus_p) >= 49);
+    }
+
+    #[test]
+    fn test_dispatch_keys_are_total() {
+        let big = Task::new(0, u32::MAX, u32::MAX);
+        assert_eq!(largest_q_plus_p(&big), 2 * u32::MAX as u64);
+
+        let ratio = |q, p| largest_q_over_p(&Task::new(0, p, q));
+        assert!(ratio(1, 3) < ratio(1, 2));
+        assert_eq!(ratio(2, 4), ratio(1, 2));
+        assert!(ratio(1, 0) > ratio(u32::MAX, 1));
+        assert_eq!(ratio(1, 0), ratio(5, 0));
+        // 0 / 0 is 0, not incomparable
+        assert_eq!(ratio(0, 0), ratio(0, 7));
+        assert!(ratio(0, 0) < ratio(1, u32::MAX));
+
+        let tasks = vec![
+            Task::new(0, 0, 0),
+            Task::new(0, 2, 3),
+            Task::new(0, 0, 5),
+            Task::new(0, 0, 0),
+            Task::new(0, 1, 4),
+        ];
+        let (order, cmax) = schrage_heaps_std_by_key(tasks, largest_q_over_p);
+        assert_eq!(
+            order,
+            vec![
+                Task::new(0, 0, 5),
+                Task::new(0, 1, 4),
+                Task::new(0, 2, 3),
+                Task::new(0, 0, 0),
+                Task::new(0, 0, 0),
+            ]
+        );
+        assert_eq!(cmax, 6);
+    }
+
     #[test]
     fn test_comparisons_rinvariant() {
         let mut heap: BinaryHeap<RInvariant> = tasks!().iter().map(|t| t.into()).collect();
@@ -150,6 +461,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::useless_conversion)]
     fn test_comparisons_qinvariant() {
         let mut heap: BinaryHeap<QInvariant> = tasks!().iter().map(|t| t.into()).collect();
         assert_eq!(heap.pop().unwrap().0, Task::new(13, 6, 26).into());
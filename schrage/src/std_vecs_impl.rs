@@ -1,19 +1,15 @@
 use crate::task::Task;
-use std::cmp::max;
+use alloc::{borrow::ToOwned, vec::Vec};
+use core::cmp::max;
 
 pub fn schrage_vecs_sort_q_cmax(mut tasks: Vec<Task>) -> u32 {
     let mut t = 0;
     let mut cmax = 0;
-    tasks.sort_by(|a, b| a.q.cmp(&b.q));
+    tasks.sort_by_key(|a| a.q);
 
     // additional scans though the vec make the code moref functional
     while !tasks.is_empty() {
-        if let Some((idx, task)) = tasks
-            .iter()
-            .enumerate()
-            .filter(|(_, task)| task.r <= t)
-            .last()
-        {
+        if let Some((idx, task)) = tasks.iter().enumerate().rfind(|(_, task)| task.r <= t) {
             t += task.p;
             cmax = max(cmax, t + task.q);
             tasks.remove(idx);
@@ -29,15 +25,10 @@ pub fn schrage_vecs_sort_q(mut tasks: Vec<Task>) -> (Vec<Task>, u32) {
     let mut t = 0;
     let mut cmax = 0;
     let mut order = Vec::new();
-    tasks.sort_by(|a, b| a.q.cmp(&b.q));
+    tasks.sort_by_key(|a| a.q);
 
     while !tasks.is_empty() {
-        if let Some((idx, task)) = tasks
-            .iter()
-            .enumerate()
-            .filter(|(_, task)| task.r <= t)
-            .last()
-        {
+        if let Some((idx, task)) = tasks.iter().enumerate().rfind(|(_, task)| task.r <= t) {
             t += task.p;
             cmax = max(cmax, t + task.q);
             order.push(tasks.remove(idx));
@@ -52,7 +43,7 @@ pub fn schrage_vecs_sort_q(mut tasks: Vec<Task>) -> (Vec<Task>, u32) {
 pub fn schrage_vecs_sort_r_cmax(mut tasks: Vec<Task>) -> u32 {
     let mut t = 0;
     let mut cmax = 0;
-    tasks.sort_by(|a, b| a.r.cmp(&b.r));
+    tasks.sort_by_key(|a| a.r);
 
     // additional scans though the vec make the code moref functional
     while !tasks.is_empty() {
@@ -77,7 +68,7 @@ pub fn schrage_vecs_sort_r(mut tasks: Vec<Task>) -> (Vec<Task>, u32) {
     let mut t = 0;
     let mut cmax = 0;
     let mut order = Vec::new();
-    tasks.sort_by(|a, b| a.r.cmp(&b.r));
+    tasks.sort_by_key(|a| a.r);
 
     // additional scans though the vec are necessary
     // the code is more functional
@@ -0,0 +1,91 @@
+use crate::task::Task;
+use alloc::vec::Vec;
+
+// instance with r and q swapped, any order of it read backwards
+// is an order of the original instance with the same cmax
+pub fn reverse(tasks: &[Task]) -> Vec<Task> {
+    tasks.iter().map(Task::reversed).collect()
+}
+
+// maps an order of the reversed instance back to the original tasks
+pub fn order_from_reversed(order: &[Task]) -> Vec<Task> {
+    order.iter().rev().map(Task::reversed).collect()
+}
+
+// runs the algorithm on the instance and on the reversed one, the better order is returned
+pub fn schrage_bidirectional(
+    tasks: Vec<Task>,
+    alg: fn(Vec<Task>) -> (Vec<Task>, u32),
+) -> (Vec<Task>, u32) {
+    let reversed = reverse(&tasks);
+    let (forward, forward_cmax) = alg(tasks);
+    let (backward, backward_cmax) = alg(reversed);
+
+    if backward_cmax < forward_cmax {
+        (order_from_reversed(&backward), backward_cmax)
+    } else {
+        (forward, forward_cmax)
+    }
+}
+
+pub fn schrage_bidirectional_cmax(tasks: Vec<Task>, alg: fn(Vec<Task>) -> u32) -> u32 {
+    let reversed = reverse(&tasks);
+    core::cmp::min(alg(tasks), alg(reversed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::custom_heap_impl::{schrage_custom_heaps, schrage_custom_heaps_cmax};
+    use crate::schedule::cmax;
+    use crate::std_heap_impl::{schrage_heaps_std, schrage_heaps_std_cmax};
+
+    macro_rules! tasks {
+        () => {
+            vec![
+                Task::new(30, 3, 8),
+                Task::new(20, 4, 21),
+                Task::new(10, 5, 7),
+                Task::new(11, 7, 24),
+                Task::new(30, 2, 0),
+                Task::new(13, 6, 26),
+                Task::new(0, 6, 17),
+            ]
+        };
+    }
+
+    #[test]
+    fn test_reversed_task() {
+        assert_eq!(Task::new(1, 2, 3).reversed(), Task::new(3, 2, 1));
+        assert_eq!(Task::new(1, 2, 3).reversed().reversed(), Task::new(1, 2, 3));
+    }
+
+    #[test]
+    fn test_reverse_keeps_cmax() {
+        let tasks = tasks!();
+        let (order, _) = schrage_heaps_std(reverse(&tasks));
+        let back = order_from_reversed(&order);
+        assert_eq!(cmax(&order), cmax(&back));
+        let mut sorted = back;
+        sorted.sort();
+        let mut expected = tasks;
+        expected.sort();
+        assert_eq!(sorted, expected);
+    }
+
+    #[test]
+    fn test_schrage_bidirectional() {
+        let (order, cmax_both) = schrage_bidirectional(tasks!(), schrage_heaps_std);
+        assert_eq!(cmax(&order), cmax_both);
+        assert!(cmax_both <= schrage_heaps_std_cmax(tasks!()));
+        let tasks = tasks!();
+        assert!(cmax_both <= schrage_heaps_std_cmax(reverse(&tasks)));
+
+        let (_, cmax_custom) = schrage_bidirectional(tasks!(), schrage_custom_heaps);
+        assert_eq!(cmax_custom, cmax_both);
+        assert_eq!(
+            schrage_bidirectional_cmax(tasks!(), schrage_custom_heaps_cmax),
+            cmax_both
+        );
+    }
+}
@@ -1,4 +1,7 @@
+use crate::due_dates::DueDateTask;
+
 #[derive(Eq, PartialEq, PartialOrd, Ord, Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Task {
     pub r: u32, // ready time
     pub p: u32, // working time
@@ -10,6 +13,68 @@ impl Task {
     pub fn new(r: u32, p: u32, q: u32) -> Task {
         Task { r, p, q }
     }
+
+    // the same task in the reversed instance, ready and cooldown times are swapped
+    pub fn reversed(&self) -> Task {
+        Task {
+            r: self.q,
+            p: self.p,
+            q: self.r,
+        }
+    }
+
+    // due date d = k - q, None when k is smaller than q
+    pub fn to_due_date_task(&self, k: u32) -> Option<DueDateTask> {
+        Some(DueDateTask::new(self.r, self.p, k.checked_sub(self.q)?))
+    }
+}
+
+// task with a weight for the weighted objectives and dispatch rules
+#[derive(Eq, PartialEq, PartialOrd, Ord, Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WeightedTask {
+    pub task: Task,
+    pub w: u32,
+}
+
+impl WeightedTask {
+    pub fn new(r: u32, p: u32, q: u32, w: u32) -> WeightedTask {
+        WeightedTask {
+            task: Task::new(r, p, q),
+            w,
+        }
+    }
+}
+
+impl From<Task> for WeightedTask {
+    fn from(task: Task) -> Self {
+        WeightedTask { task, w: 1 }
+    }
+}
+
+// anything scheduled like a task, plain tasks have weight 1
+pub trait Job: Copy {
+    fn task(&self) -> &Task;
+
+    fn weight(&self) -> u32 {
+        1
+    }
+}
+
+impl Job for Task {
+    fn task(&self) -> &Task {
+        self
+    }
+}
+
+impl Job for WeightedTask {
+    fn task(&self) -> &Task {
+        &self.task
+    }
+
+    fn weight(&self) -> u32 {
+        self.w
+    }
 }
 
 impl From<RInvariant> for Task {
@@ -24,14 +89,14 @@ impl From<QInvariant> for Task {
     }
 }
 
-use std::fmt::Display;
+use core::fmt::Display;
 impl Display for Task {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "({}, {}, {})", self.r, self.p, self.q)
     }
 }
 
-use std::cmp::Ordering;
+use core::cmp::Ordering;
 
 #[derive(Eq, Debug)]
 pub struct QInvariant(pub Task);
@@ -56,7 +121,7 @@ impl PartialEq for RInvariant {
 
 impl PartialOrd for RInvariant {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(other.0.r.cmp(&self.0.r))
+        Some(self.cmp(other))
     }
 }
 // since RInvariant has to be in descending order
@@ -90,7 +155,7 @@ impl PartialEq for QInvariant {
 
 impl PartialOrd for QInvariant {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.0.q.cmp(&other.0.q))
+        Some(self.cmp(other))
     }
 }
 
@@ -99,3 +164,100 @@ impl Ord for QInvariant {
         self.0.q.cmp(&other.0.q)
     }
 }
+
+// heap element that holds a job, lets the schrage loops run on any of the wrappers
+pub trait Entry<J> {
+    fn job(&self) -> &J;
+    fn into_job(self) -> J;
+}
+
+impl Entry<Task> for RInvariant {
+    fn job(&self) -> &Task {
+        &self.0
+    }
+
+    fn into_job(self) -> Task {
+        self.0
+    }
+}
+
+impl Entry<Task> for QInvariant {
+    fn job(&self) -> &Task {
+        &self.0
+    }
+
+    fn into_job(self) -> Task {
+        self.0
+    }
+}
+
+impl<K, J> Entry<J> for KeyInvariant<K, J> {
+    fn job(&self) -> &J {
+        &self.1
+    }
+
+    fn into_job(self) -> J {
+        self.1
+    }
+}
+
+// job ordered by a dispatch key, the largest key is on top of the heap
+#[derive(Debug)]
+pub struct KeyInvariant<K, J = Task>(pub K, pub J);
+
+impl<K: Ord, J> PartialEq for KeyInvariant<K, J> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<K: Ord, J> Eq for KeyInvariant<K, J> {}
+
+impl<K: Ord, J> PartialOrd for KeyInvariant<K, J> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K: Ord, J> Ord for KeyInvariant<K, J> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+// num / den as a dispatch key, compared exactly by cross multiplication,
+// x / 0 is larger than every finite ratio and 0 / 0 is taken as 0
+#[derive(Debug, Clone, Copy)]
+pub struct Ratio {
+    num: u32,
+    den: u32,
+}
+
+impl Ratio {
+    pub fn new(num: u32, den: u32) -> Ratio {
+        match (num, den) {
+            (0, 0) => Ratio { num: 0, den: 1 },
+            _ => Ratio { num, den },
+        }
+    }
+}
+
+impl PartialEq for Ratio {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Ratio {}
+
+impl PartialOrd for Ratio {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Ratio {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.num as u64 * other.den as u64).cmp(&(other.num as u64 * self.den as u64))
+    }
+}
@@ -6,10 +6,18 @@ use std::path::PathBuf;
 use lazy_static::lazy_static;
 use paste::paste;
 
+use crate::calendar::*;
+use crate::carlier::*;
 use crate::custom_heap_impl::*;
+use crate::heuristics::*;
+use crate::local_search::*;
+use crate::multi_machine::*;
+use crate::soa_impl::*;
 use crate::std_heap_impl::*;
 use crate::std_vecs_impl::*;
 use crate::task::Task;
+use crate::trace::*;
+use crate::validation::*;
 
 #[derive(Debug, Default)]
 struct TestData {
@@ -52,6 +60,18 @@ macro_rules! generate_algorithm_tests_preemptive {
     };
 }
 
+// algorithms that can only improve on schrage are checked against both bounds
+macro_rules! generate_algorithm_tests_bounded {
+    ($test_name:ident, $alg_func:ident, $idx:expr) => {
+        #[test]
+        fn $test_name() {
+            let cmax = $alg_func(TEST_DATA[$idx].data.clone());
+            assert!(cmax <= TEST_DATA[$idx].cmax);
+            assert!(cmax >= TEST_DATA[$idx].cmax_preemptive);
+        }
+    };
+}
+
 macro_rules! test_alg {
     ($alg_name:ident, $($test_case:expr),*) => {
         paste!{
@@ -72,14 +92,286 @@ macro_rules! test_alg_preemptive {
     }
 }
 
+macro_rules! test_alg_bounded {
+    ($alg_name:ident, $($test_case:expr),*) => {
+        paste!{
+            $(
+                generate_algorithm_tests_bounded!([<$alg_name _test $test_case>], $alg_name, $test_case);
+            )*
+        }
+    }
+}
+
 // test cases from the website
 test_alg!(schrage_vecs_sort_q_cmax, 0, 1, 2, 3, 5, 6, 7);
 test_alg!(schrage_vecs_sort_r_cmax, 0, 1, 2, 3, 5, 6, 7);
 test_alg!(schrage_custom_heaps_cmax, 0, 1, 2, 3, 5, 6, 7);
 test_alg!(schrage_heaps_std_cmax, 0, 1, 2, 3, 5, 6, 7);
+test_alg!(schrage_soa_heaps_cmax, 0, 1, 2, 3, 5, 6, 7);
 test_alg_preemptive!(schrage_preemptive_heaps_std_cmax, 0, 1, 2, 3, 5, 6, 7);
 test_alg_preemptive!(schrage_preemptive_custom_heaps_cmax, 0, 1, 2, 3, 5, 6, 7);
 test_alg_preemptive!(schrage_preemptive_vecs_cmax, 0, 1, 3, 5);
+test_alg_bounded!(potts_cmax, 0, 1, 2, 3, 4, 5, 6, 7);
+test_alg_bounded!(hall_shmoys_cmax, 0, 1, 2, 3, 4, 5, 6, 7);
+// the remaining instances need too many branches for a debug build
+test_alg_bounded!(carlier_cmax, 0, 1, 3, 4);
+
+#[test]
+fn carlier_not_worse_than_heuristics() {
+    for idx in [0, 1, 3, 4] {
+        let data = &TEST_DATA[idx].data;
+        assert!(carlier_cmax(data.clone()) <= hall_shmoys_cmax(data.clone()));
+    }
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn parallel_carlier_matches_carlier() {
+    for idx in [0, 1, 3, 4] {
+        let data = &TEST_DATA[idx].data;
+        assert_eq!(
+            carlier_parallel_cmax(data.clone()),
+            carlier_cmax(data.clone())
+        );
+    }
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn batch_matches_single_runs() {
+    let instances = TEST_DATA.iter().map(|data| data.data.clone());
+    let cmaxes = crate::batch::solve_batch(instances, schrage_heaps_std_cmax);
+    let expected: Vec<u32> = TEST_DATA.iter().map(|data| data.cmax).collect();
+    assert_eq!(cmaxes, expected);
+}
+
+#[test]
+fn local_search_not_worse_than_schrage() {
+    for data in TEST_DATA.iter() {
+        let schedule = schrage_local_search(data.data.clone(), &LocalSearchConfig::default());
+        assert!(schedule.cmax <= data.cmax);
+        assert!(schedule.cmax >= data.cmax_preemptive);
+    }
+}
+
+#[test]
+fn empty_calendar_matches_schrage() {
+    let calendar = Calendar::default();
+    for idx in [0, 1, 2, 3, 5, 6, 7] {
+        let data = &TEST_DATA[idx];
+        assert_eq!(
+            schrage_calendar_cmax(data.data.clone(), &calendar),
+            data.cmax
+        );
+        assert_eq!(
+            schrage_preemptive_calendar_cmax(data.data.clone(), &calendar),
+            data.cmax_preemptive
+        );
+    }
+}
+
+#[test]
+fn multi_machine_bounds() {
+    for data in TEST_DATA.iter() {
+        assert_eq!(
+            schrage_multi_machine_cmax(data.data.clone(), 1),
+            Some(data.cmax)
+        );
+        assert_eq!(
+            multi_machine_lower_bound(data.data.clone(), 1),
+            Some(data.cmax_preemptive)
+        );
+        for machines in 2..5 {
+            let cmax = schrage_multi_machine_cmax(data.data.clone(), machines);
+            assert!(cmax >= multi_machine_lower_bound(data.data.clone(), machines));
+        }
+    }
+}
+
+#[test]
+fn hall_shmoys_not_worse_than_potts() {
+    for data in TEST_DATA.iter() {
+        assert!(hall_shmoys_cmax(data.data.clone()) <= potts_cmax(data.data.clone()));
+    }
+}
+
+#[test]
+fn schrage_orders_are_valid() {
+    for data in TEST_DATA.iter() {
+        let (order, _) = schrage_heaps_std(data.data.clone());
+        let order = crate::schedule::order_indices(&order, &data.data);
+        assert_eq!(validate_order(&data.data, &order), Ok(data.cmax));
+    }
+}
+
+#[test]
+fn preemptive_segments_are_valid() {
+    for data in TEST_DATA.iter() {
+        let (segments, cmax) = schrage_preemptive_segments(&data.data);
+        assert_eq!(cmax, data.cmax_preemptive);
+        assert_eq!(validate_segments(&data.data, &segments), Ok(cmax));
+    }
+}
+
+#[test]
+fn io_reads_schr_data() {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test_data/schr.data");
+    let instances = crate::io::read_schr_data(path).unwrap();
+    assert_eq!(instances.len(), TEST_DATA.len());
+    for ((name, tasks), data) in instances.iter().zip(TEST_DATA.iter()) {
+        assert_eq!(format!("{name}:"), data._data_name);
+        assert_eq!(tasks, &data.data);
+    }
+}
+
+#[test]
+fn schr_data_round_trip() {
+    let data: String = TEST_DATA
+        .iter()
+        .map(|data| crate::io::to_schr_data(data._data_name.trim_end_matches(':'), &data.data))
+        .collect();
+    let instances = crate::io::parse_schr_data(&data).unwrap();
+    for ((_, tasks), data) in instances.iter().zip(TEST_DATA.iter()) {
+        assert_eq!(tasks, &data.data);
+    }
+    // the written results are the ones from the website
+    for data in TEST_DATA.iter() {
+        let written = crate::io::to_schr_data("data", &data.data);
+        assert!(written.contains(&format!("schrpmtn:\n{}\n", data.cmax_preemptive)));
+        assert!(written.contains(&format!("schr:\n{}\n", data.cmax)));
+    }
+}
+
+#[test]
+fn slice_entry_points_match() {
+    let mut std_scratch = SchrageScratch::new();
+    let mut custom_scratch = CustomHeapsScratch::new();
+    let mut order = Vec::new();
+    for data in TEST_DATA.iter() {
+        assert_eq!(schrage_heaps_std_slice_cmax(&data.data), data.cmax);
+        assert_eq!(schrage_custom_heaps_slice_cmax(&data.data), data.cmax);
+        assert_eq!(std_scratch.schrage_into(&data.data, &mut order), data.cmax);
+        assert_eq!(order, schrage_heaps_std(data.data.clone()).0);
+        assert_eq!(
+            custom_scratch.schrage_into(&data.data, &mut order),
+            data.cmax
+        );
+        assert_eq!(
+            std_scratch.schrage_preemptive_cmax(&data.data),
+            data.cmax_preemptive
+        );
+        assert_eq!(
+            custom_scratch.schrage_preemptive_cmax(&data.data),
+            data.cmax_preemptive
+        );
+    }
+}
+
+#[test]
+fn trace_folds_to_schrage_cmax() {
+    for idx in [0, 1, 2, 3, 5, 6, 7] {
+        let data = &TEST_DATA[idx];
+        assert_eq!(trace_cmax(&data.data, schrage_trace(&data.data)), data.cmax);
+        assert_eq!(
+            trace_cmax(&data.data, schrage_preemptive_trace(&data.data)),
+            data.cmax_preemptive
+        );
+    }
+}
+
+#[test]
+fn soa_orders_match_std_heaps() {
+    for data in TEST_DATA.iter() {
+        assert_eq!(
+            schrage_soa_heaps(data.data.clone()),
+            schrage_heaps_std(data.data.clone())
+        );
+    }
+}
+
+#[test]
+fn array_heaps_match_schrage() {
+    let mut available: heap::ArrayHeap<_, 64> = heap::ArrayHeap::new();
+    let mut unavailable: heap::ArrayHeap<_, 64> = heap::ArrayHeap::new();
+    for idx in [0, 1, 2, 3, 5, 6, 7] {
+        let data = &TEST_DATA[idx];
+        assert_eq!(
+            schrage_generic_heaps_cmax(&data.data, &mut available, &mut unavailable),
+            Ok(data.cmax)
+        );
+    }
+}
+
+#[test]
+fn csv_round_trip() {
+    for data in TEST_DATA.iter() {
+        let csv = crate::io::to_csv(&data.data);
+        assert_eq!(crate::io::parse_csv(&csv).as_ref(), Some(&data.data));
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn json_round_trip() {
+    use crate::schedule::Schedule;
+
+    for data in TEST_DATA.iter() {
+        let json = serde_json::to_string(&data.data).unwrap();
+        assert_eq!(serde_json::from_str::<Vec<Task>>(&json).unwrap(), data.data);
+
+        let schedule = Schedule::from(schrage_heaps_std(data.data.clone()));
+        let json = serde_json::to_string(&schedule).unwrap();
+        assert_eq!(serde_json::from_str::<Schedule>(&json).unwrap(), schedule);
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn json_round_trip_results() {
+    use crate::due_dates::{deadline_feasibility, to_due_dates, Feasibility};
+    use crate::jobshop::{shifting_bottleneck_carlier, JobShop, JobShopSchedule};
+    use crate::objectives::{evaluate, Objectives};
+    use crate::precedence::PrecedenceViolation;
+    use serde::{de::DeserializeOwned, Serialize};
+
+    fn round_trip<T: Serialize + DeserializeOwned + PartialEq + core::fmt::Debug>(value: &T) {
+        let json = serde_json::to_string(value).unwrap();
+        assert_eq!(&serde_json::from_str::<T>(&json).unwrap(), value);
+    }
+
+    for data in TEST_DATA.iter().filter(|data| data.data_size <= 50) {
+        let (due, _) = to_due_dates(&data.data);
+        round_trip::<Feasibility>(&deadline_feasibility(due));
+        let (order, segments) = (
+            schrage_heaps_std(data.data.clone()).0,
+            schrage_preemptive_segments(&data.data).0,
+        );
+        round_trip::<Objectives>(&evaluate(&order, 0));
+        round_trip::<Vec<Segment>>(&segments);
+    }
+    round_trip::<Feasibility>(&Feasibility::InfeasiblePreemptive);
+    round_trip(&PrecedenceViolation::Violated {
+        predecessor: 1,
+        successor: 0,
+    });
+    round_trip(&PrecedenceViolation::InvalidOrder(
+        ScheduleError::MissingTask(2),
+    ));
+    round_trip(&ScheduleError::TimeOverflow { task: 0, start: 7 });
+    round_trip(&crate::metaheuristics::Progress {
+        iteration: 3,
+        elapsed: std::time::Duration::from_millis(12),
+        current_cmax: 60,
+        best_cmax: 55,
+        lower_bound: 50,
+    });
+
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("test_data/ft06.data");
+    let instance = JobShop::parse(&std::fs::read_to_string(path).unwrap()).unwrap();
+    round_trip::<JobShop>(&instance);
+    round_trip::<JobShopSchedule>(&shifting_bottleneck_carlier(&instance));
+}
 
 // looks kinda ugly but gets the parsing done
 fn parse_test_file(filename: &str) -> Vec<TestData> {
@@ -0,0 +1,245 @@
+use crate::soa_impl::{ByQ, ByR, IndexHeap, TaskArrays};
+use crate::task::Task;
+use alloc::vec::Vec;
+use core::cmp::{max, Reverse};
+
+// what happens on the machine, tasks are indices into the instance
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Event {
+    // the task joins the ready queue
+    TaskReleased(usize),
+    // also when a preempted task is resumed
+    TaskStarted(usize),
+    // the task goes back to the ready queue with the remaining processing time
+    TaskPreempted { task: usize, remaining: u32 },
+    TaskCompleted(usize),
+    // nothing is ready, the machine waits for the next release
+    IdleUntil(u32),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Step {
+    pub time: u32,
+    pub event: Event,
+    // ready queue after the event, by q from the largest
+    pub ready: Vec<usize>,
+}
+
+// the schrage loop run one event at a time, events come in the order of their time
+// the heaps break ties like the std heaps so the non preemptive schedule is the one
+// of schrage_heaps_std
+pub struct SchrageTrace {
+    tasks: TaskArrays,
+    preemptive: bool,
+    available_tasks: IndexHeap<ByQ>,
+    unavailable_tasks: IndexHeap<ByR>,
+    remaining: Vec<u32>,
+    t: u32,
+    // running task and the time it completes
+    running: Option<(u32, u32)>,
+}
+
+impl SchrageTrace {
+    fn new(tasks: &[Task], preemptive: bool) -> SchrageTrace {
+        let tasks = TaskArrays::from(tasks);
+        let mut unavailable_tasks = IndexHeap::with_capacity(tasks.len());
+        unavailable_tasks.fill(&tasks);
+        SchrageTrace {
+            preemptive,
+            available_tasks: IndexHeap::with_capacity(tasks.len()),
+            unavailable_tasks,
+            remaining: tasks.p.clone(),
+            t: 0,
+            running: None,
+            tasks,
+        }
+    }
+
+    fn step(&self, event: Event) -> Step {
+        let mut ready: Vec<usize> = self
+            .available_tasks
+            .as_slice()
+            .iter()
+            .map(|&idx| idx as usize)
+            .collect();
+        ready.sort_by_key(|&idx| (Reverse(self.tasks.q[idx]), idx));
+        Step {
+            time: self.t,
+            event,
+            ready,
+        }
+    }
+
+    fn next_release(&self) -> Option<u32> {
+        let idx = self.unavailable_tasks.peek()?;
+        Some(self.tasks.r[idx as usize])
+    }
+
+    fn release(&mut self) -> Step {
+        let idx = self.unavailable_tasks.pop(&self.tasks).unwrap();
+        self.t = self.tasks.r[idx as usize];
+        self.available_tasks.push(&self.tasks, idx);
+        self.step(Event::TaskReleased(idx as usize))
+    }
+}
+
+impl Iterator for SchrageTrace {
+    type Item = Step;
+
+    fn next(&mut self) -> Option<Step> {
+        let next_release = self.next_release();
+        if let Some((task, end)) = self.running {
+            // a ready task with a longer tail takes the machine over
+            let urgent = self
+                .available_tasks
+                .peek()
+                .map(|idx| self.tasks.q[idx as usize]);
+            if self.preemptive && urgent > Some(self.tasks.q[task as usize]) {
+                self.running = None;
+                let remaining = end - self.t;
+                self.remaining[task as usize] = remaining;
+                self.available_tasks.push(&self.tasks, task);
+                let task = task as usize;
+                return Some(self.step(Event::TaskPreempted { task, remaining }));
+            }
+            // tasks released at the completion time are handled after it
+            if next_release.is_some_and(|r| r < end) {
+                return Some(self.release());
+            }
+            self.running = None;
+            self.t = end;
+            return Some(self.step(Event::TaskCompleted(task as usize)));
+        }
+
+        if next_release.is_some_and(|r| r <= self.t) {
+            return Some(self.release());
+        }
+        if let Some(idx) = self.available_tasks.pop(&self.tasks) {
+            self.running = Some((idx, self.t + self.remaining[idx as usize]));
+            return Some(self.step(Event::TaskStarted(idx as usize)));
+        }
+        let r = next_release?;
+        let step = self.step(Event::IdleUntil(r));
+        self.t = r;
+        Some(step)
+    }
+}
+
+pub fn schrage_trace(tasks: &[Task]) -> SchrageTrace {
+    SchrageTrace::new(tasks, false)
+}
+
+pub fn schrage_preemptive_trace(tasks: &[Task]) -> SchrageTrace {
+    SchrageTrace::new(tasks, true)
+}
+
+// cmax as a fold over the events, the latest completion plus the tail of the task
+pub fn trace_cmax(tasks: &[Task], trace: impl IntoIterator<Item = Step>) -> u32 {
+    trace.into_iter().fold(0, |cmax, step| match step.event {
+        Event::TaskCompleted(task) => max(cmax, step.time + tasks[task].q),
+        _ => cmax,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::Generator;
+    use crate::std_heap_impl::{schrage_heaps_std, schrage_preemptive_heaps_std_cmax};
+
+    macro_rules! tasks {
+        () => {
+            vec![
+                Task::new(30, 3, 8),
+                Task::new(20, 4, 21),
+                Task::new(10, 5, 7),
+                Task::new(11, 7, 24),
+                Task::new(30, 2, 0),
+                Task::new(13, 6, 26),
+                Task::new(0, 6, 17),
+            ]
+        };
+    }
+
+    #[test]
+    fn test_trace_events() {
+        let tasks = vec![Task::new(0, 4, 1), Task::new(2, 3, 5), Task::new(9, 1, 0)];
+        let steps: Vec<Step> = schrage_trace(&tasks).collect();
+        let events: Vec<(u32, Event)> = steps.iter().map(|s| (s.time, s.event)).collect();
+        assert_eq!(
+            events,
+            vec![
+                (0, Event::TaskReleased(0)),
+                (0, Event::TaskStarted(0)),
+                (2, Event::TaskReleased(1)),
+                (4, Event::TaskCompleted(0)),
+                (4, Event::TaskStarted(1)),
+                (7, Event::TaskCompleted(1)),
+                (7, Event::IdleUntil(9)),
+                (9, Event::TaskReleased(2)),
+                (9, Event::TaskStarted(2)),
+                (10, Event::TaskCompleted(2)),
+            ]
+        );
+        assert_eq!(steps[0].ready, vec![0]);
+        assert_eq!(steps[2].ready, vec![1]);
+        assert_eq!(trace_cmax(&tasks, steps), 12);
+
+        let events: Vec<(u32, Event)> = schrage_preemptive_trace(&tasks)
+            .map(|s| (s.time, s.event))
+            .collect();
+        assert_eq!(
+            events[2..6],
+            [
+                (2, Event::TaskReleased(1)),
+                (
+                    2,
+                    Event::TaskPreempted {
+                        task: 0,
+                        remaining: 2
+                    }
+                ),
+                (2, Event::TaskStarted(1)),
+                (5, Event::TaskCompleted(1)),
+            ]
+        );
+        assert_eq!(trace_cmax(&tasks, schrage_preemptive_trace(&tasks)), 10);
+    }
+
+    #[test]
+    fn test_trace_matches_schrage() {
+        let tasks = tasks!();
+        assert_eq!(trace_cmax(&tasks, schrage_trace(&tasks)), 53);
+        assert_eq!(trace_cmax(&tasks, schrage_preemptive_trace(&tasks)), 49);
+        assert_eq!(schrage_trace(&[]).next(), None);
+
+        let mut generator = Generator::new(3);
+        for n in (0..200).step_by(4) {
+            let tasks = generator.uniform(n, &(0..(n as u32 * 3 + 1)), &(1..10), &(0..20));
+            let (order, cmax) = schrage_heaps_std(tasks.clone());
+            let completed: Vec<Task> = schrage_trace(&tasks)
+                .filter_map(|step| match step.event {
+                    Event::TaskCompleted(task) => Some(tasks[task]),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(completed, order);
+            assert_eq!(trace_cmax(&tasks, schrage_trace(&tasks)), cmax);
+            assert_eq!(
+                trace_cmax(&tasks, schrage_preemptive_trace(&tasks)),
+                schrage_preemptive_heaps_std_cmax(tasks)
+            );
+        }
+    }
+
+    #[test]
+    fn test_trace_time_does_not_go_back() {
+        let tasks = tasks!();
+        for trace in [schrage_trace(&tasks), schrage_preemptive_trace(&tasks)] {
+            let times: Vec<u32> = trace.map(|step| step.time).collect();
+            assert!(times.windows(2).all(|w| w[0] <= w[1]));
+        }
+    }
+}
@@ -0,0 +1,328 @@
+use crate::task::Task;
+use alloc::{vec, vec::Vec};
+use core::cmp::max;
+
+// piece of a task processed in [start, end), a non-preemptive schedule has one per task
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Segment {
+    pub task: usize,
+    pub start: u32,
+    pub end: u32,
+}
+
+impl Segment {
+    pub fn new(task: usize, start: u32, end: u32) -> Segment {
+        Segment { task, start, end }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ScheduleError {
+    // index that is not a task of the instance
+    UnknownTask(usize),
+    MissingTask(usize),
+    DuplicateTask(usize),
+    // segment that ends before it starts
+    InvalidSegment(Segment),
+    StartsBeforeRelease {
+        task: usize,
+        start: u32,
+        r: u32,
+    },
+    Overlap {
+        first: Segment,
+        second: Segment,
+    },
+    // processed time of a task differs from its p
+    WrongProcessingTime {
+        task: usize,
+        expected: u32,
+        processed: u32,
+    },
+    // not one start time per task of the order
+    LengthMismatch {
+        order: usize,
+        start_times: usize,
+    },
+    // a time of the task counted from start does not fit in u32
+    TimeOverflow {
+        task: usize,
+        start: u32,
+    },
+}
+
+// every one of n tasks appears exactly once in the order
+pub(crate) fn validate_permutation(n: usize, order: &[usize]) -> Result<(), ScheduleError> {
+    let mut seen = vec![false; n];
+    for &task in order {
+        if task >= n {
+            return Err(ScheduleError::UnknownTask(task));
+        }
+        if seen[task] {
+            return Err(ScheduleError::DuplicateTask(task));
+        }
+        seen[task] = true;
+    }
+    match seen.iter().position(|&s| !s) {
+        Some(missing) => Err(ScheduleError::MissingTask(missing)),
+        None => Ok(()),
+    }
+}
+
+// order given as task indices, every task starts as soon as possible,
+// returns the recomputed cmax
+pub fn validate_order(tasks: &[Task], order: &[usize]) -> Result<u32, ScheduleError> {
+    validate_permutation(tasks.len(), order)?;
+
+    let mut t = 0;
+    let mut cmax = 0;
+    for &idx in order {
+        let task = &tasks[idx];
+        let start = max(t, task.r);
+        let overflow = ScheduleError::TimeOverflow { task: idx, start };
+        t = start.checked_add(task.p).ok_or(overflow)?;
+        cmax = max(cmax, t.checked_add(task.q).ok_or(overflow)?);
+    }
+    Ok(cmax)
+}
+
+// timed pieces of a possibly preemptive schedule in any order, returns the recomputed cmax
+pub fn validate_segments(tasks: &[Task], segments: &[Segment]) -> Result<u32, ScheduleError> {
+    let mut processed = vec![0u32; tasks.len()];
+    let mut completion = vec![None; tasks.len()];
+    for segment in segments {
+        let Some(task) = tasks.get(segment.task) else {
+            return Err(ScheduleError::UnknownTask(segment.task));
+        };
+        if segment.end < segment.start {
+            return Err(ScheduleError::InvalidSegment(*segment));
+        }
+        if segment.start < task.r {
+            return Err(ScheduleError::StartsBeforeRelease {
+                task: segment.task,
+                start: segment.start,
+                r: task.r,
+            });
+        }
+        let overflow = ScheduleError::TimeOverflow {
+            task: segment.task,
+            start: segment.start,
+        };
+        processed[segment.task] = processed[segment.task]
+            .checked_add(segment.end - segment.start)
+            .ok_or(overflow)?;
+        completion[segment.task] = max(completion[segment.task], Some(segment.end));
+    }
+    if let Some(missing) = completion.iter().position(Option::is_none) {
+        return Err(ScheduleError::MissingTask(missing));
+    }
+    for (idx, task) in tasks.iter().enumerate() {
+        if processed[idx] != task.p {
+            return Err(ScheduleError::WrongProcessingTime {
+                task: idx,
+                expected: task.p,
+                processed: processed[idx],
+            });
+        }
+    }
+
+    // empty pieces take no machine time and cannot overlap
+    let mut timeline: Vec<Segment> = segments
+        .iter()
+        .filter(|s| s.start < s.end)
+        .copied()
+        .collect();
+    timeline.sort_by_key(|s| (s.start, s.end));
+    for pair in timeline.windows(2) {
+        if pair[0].end > pair[1].start {
+            return Err(ScheduleError::Overlap {
+                first: pair[0],
+                second: pair[1],
+            });
+        }
+    }
+
+    let mut cmax = 0;
+    for (idx, (task, c)) in tasks.iter().zip(completion).enumerate() {
+        // unwrap is safe, missing tasks were rejected above
+        let c = c.unwrap();
+        let overflow = ScheduleError::TimeOverflow {
+            task: idx,
+            start: c,
+        };
+        cmax = max(cmax, c.checked_add(task.q).ok_or(overflow)?);
+    }
+    Ok(cmax)
+}
+
+// non-preemptive schedule with explicit start times in the given order
+pub fn validate_start_times(
+    tasks: &[Task],
+    order: &[usize],
+    start_times: &[u32],
+) -> Result<u32, ScheduleError> {
+    validate_order(tasks, order)?;
+    if order.len() != start_times.len() {
+        return Err(ScheduleError::LengthMismatch {
+            order: order.len(),
+            start_times: start_times.len(),
+        });
+    }
+    let segments = order
+        .iter()
+        .zip(start_times)
+        .map(|(&task, &start)| match start.checked_add(tasks[task].p) {
+            Some(end) => Ok(Segment::new(task, start, end)),
+            None => Err(ScheduleError::TimeOverflow { task, start }),
+        })
+        .collect::<Result<Vec<Segment>, ScheduleError>>()?;
+    validate_segments(tasks, &segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schedule::start_times;
+
+    macro_rules! tasks {
+        () => {
+            vec![
+                Task::new(30, 3, 8),
+                Task::new(20, 4, 21),
+                Task::new(10, 5, 7),
+                Task::new(11, 7, 24),
+                Task::new(30, 2, 0),
+                Task::new(13, 6, 26),
+                Task::new(0, 6, 17),
+            ]
+        };
+    }
+
+    #[test]
+    fn test_validate_order() {
+        let tasks = tasks!();
+        // schrage order of the example
+        let order = [6, 2, 5, 3, 1, 0, 4];
+        assert_eq!(validate_order(&tasks, &order), Ok(53));
+        assert_eq!(
+            validate_order(&tasks, &[6, 2, 5, 3, 1, 0, 7]),
+            Err(ScheduleError::UnknownTask(7))
+        );
+        assert_eq!(
+            validate_order(&tasks, &[6, 2, 5, 3, 1, 0, 0]),
+            Err(ScheduleError::DuplicateTask(0))
+        );
+        assert_eq!(
+            validate_order(&tasks, &[6, 2, 5, 3, 1, 0]),
+            Err(ScheduleError::MissingTask(4))
+        );
+    }
+
+    #[test]
+    fn test_validate_start_times() {
+        let tasks = tasks!();
+        let order = [6, 2, 5, 3, 1, 0, 4];
+        let ordered: Vec<Task> = order.iter().map(|&j| tasks[j]).collect();
+        let starts = start_times(&ordered);
+        assert_eq!(validate_start_times(&tasks, &order, &starts), Ok(53));
+
+        let mut early = starts.clone();
+        early[0] = 0;
+        early[1] = 5;
+        assert_eq!(
+            validate_start_times(&tasks, &order, &early),
+            Err(ScheduleError::StartsBeforeRelease {
+                task: 2,
+                start: 5,
+                r: 10
+            })
+        );
+
+        let mut overlapping = starts.clone();
+        overlapping[3] -= 1;
+        assert_eq!(
+            validate_start_times(&tasks, &order, &overlapping),
+            Err(ScheduleError::Overlap {
+                first: Segment::new(5, starts[2], starts[2] + 6),
+                second: Segment::new(3, starts[3] - 1, starts[3] + 6),
+            })
+        );
+
+        assert_eq!(
+            validate_start_times(&tasks, &order, &starts[..6]),
+            Err(ScheduleError::LengthMismatch {
+                order: 7,
+                start_times: 6
+            })
+        );
+        let mut late = starts.clone();
+        late[6] = u32::MAX;
+        assert_eq!(
+            validate_start_times(&tasks, &order, &late),
+            Err(ScheduleError::TimeOverflow {
+                task: 4,
+                start: u32::MAX
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_segments() {
+        let tasks = vec![Task::new(0, 4, 1), Task::new(1, 2, 10)];
+        // the second task preempts the first one
+        let segments = [
+            Segment::new(0, 0, 1),
+            Segment::new(1, 1, 3),
+            Segment::new(0, 3, 6),
+        ];
+        assert_eq!(validate_segments(&tasks, &segments), Ok(13));
+        assert_eq!(
+            validate_segments(&tasks, &segments[..2]),
+            Err(ScheduleError::WrongProcessingTime {
+                task: 0,
+                expected: 4,
+                processed: 1
+            })
+        );
+        assert_eq!(
+            validate_segments(&tasks, &[Segment::new(0, 0, 4)]),
+            Err(ScheduleError::MissingTask(1))
+        );
+        assert_eq!(
+            validate_segments(&tasks, &[Segment::new(0, 4, 0)]),
+            Err(ScheduleError::InvalidSegment(Segment::new(0, 4, 0)))
+        );
+    }
+
+    #[test]
+    fn test_time_overflow() {
+        let late = vec![Task::new(u32::MAX - 1, 5, 0)];
+        assert_eq!(
+            validate_order(&late, &[0]),
+            Err(ScheduleError::TimeOverflow {
+                task: 0,
+                start: u32::MAX - 1
+            })
+        );
+        let long_tail = vec![Task::new(0, 1, 0), Task::new(0, 1, u32::MAX)];
+        assert_eq!(
+            validate_order(&long_tail, &[0, 1]),
+            Err(ScheduleError::TimeOverflow { task: 1, start: 1 })
+        );
+        assert_eq!(
+            validate_segments(&long_tail, &[Segment::new(0, 0, 1), Segment::new(1, 1, 2)]),
+            Err(ScheduleError::TimeOverflow { task: 1, start: 2 })
+        );
+        // the pieces of one task add up to more than u32::MAX
+        let long = vec![Task::new(0, u32::MAX, 0)];
+        assert_eq!(
+            validate_segments(
+                &long,
+                &[Segment::new(0, 0, u32::MAX), Segment::new(0, 1, 3)]
+            ),
+            Err(ScheduleError::TimeOverflow { task: 0, start: 1 })
+        );
+    }
+}